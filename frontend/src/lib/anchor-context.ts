@@ -14,7 +14,11 @@ import {
 import PocketBase from 'pocketbase';
 
 export const PROGRAM_ID = new PublicKey("H7SvZyDYbZ7ioeCTYWE4wuVdgWUyA5XJ1CAZnsH7ga8E");
-export const PROGRAM_TREASURY = new PublicKey("FuFzoMF5xTwZego84fRoscnart4dPYNkpHho2UBe7NDt");
+// Creation and platform fees land in this PDA; the config admin withdraws them with withdrawRevenue
+export const [REVENUE_TREASURY] = PublicKey.findProgramAddressSync(
+  [Buffer.from("revenue_treasury")],
+  PROGRAM_ID
+);
 export const CPT_TOKEN_MINT = new PublicKey("wc3eLDaYLrPwD6Xacvb4xfXD1Cu6Mcw7ZbWopNynNYT");
 export const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"); // Regular Token program
 
@@ -44,6 +48,8 @@ type ChallengeAccount = {
   max_participants: number;  // Changed from maxParticipants
   submission_votes: [PublicKey, anchor.BN][]; // Changed from submissionVotes
   voters: [PublicKey, PublicKey][]; 
+  submitters: [PublicKey, PublicKey][]; // (submission_id, submitter wallet)
+  winning_submission: PublicKey | null;
   [key: string]: any; // Index signature allowing access with string keys
 };
 
@@ -166,7 +172,7 @@ export function useAnchorContextProvider(): AnchorContextType {
           challenge: challengeKeypair.publicKey,
           treasury: treasuryPDA,
           votingTreasury: votingTreasuryPDA,
-          programAccount: PROGRAM_TREASURY,
+          programAccount: REVENUE_TREASURY,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
           tokenMint: CPT_TOKEN_MINT,
@@ -1079,6 +1085,7 @@ export function useAnchorContextProvider(): AnchorContextType {
           treasuryTokenAccount: treasuryTokenAccount,
           creatorTokenAccount: creatorTokenAccount,
           creator: creatorPubkey, // Using the blockchain-extracted creator
+          winner: winnerPubkey, // Must be the wallet that submitted the winning video
           systemProgram: SystemProgram.programId,
         })
         .rpc();
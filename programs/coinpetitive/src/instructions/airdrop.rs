@@ -0,0 +1,454 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::instructions::emission::EmissionLedger;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
+use crate::instructions::token_errors::TokenError;
+use crate::instructions::user_stats::{ActivitySnapshot, EpochActivity};
+
+/*
+    merkle airdrops out of the airdrop vault
+    the admin posts a root, the total amount and an expiry, and the total moves into
+    an escrow owned by the vault authority
+    recipients claim their own leaf with a proof, a bitmap stops double claims
+    after expiry anyone can send what is left back to the emission reserve
+    an airdrop created from an activity snapshot takes its root from the snapshot and
+    only pays claimants whose EpochActivity shows activity in that epoch
+
+    leaf = sha256(0x00 || index u32 le || claimant || amount u64 le)
+    node = sha256(0x01 || min(a, b) || max(a, b))
+*/
+
+pub const MAX_AIRDROP_RECIPIENTS: u32 = 65_536; // 8KB bitmap, under the 10KB init limit
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+#[account]
+pub struct MerkleDistributor {
+    pub airdrop_id: u64,
+    pub root: [u8; 32],
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub num_recipients: u32,
+    pub num_claimed: u32,
+    pub expires_at: i64,
+    pub reclaimed: bool,
+    pub activity_epoch: Option<u64>, // Set when the root comes from an activity snapshot
+    pub claimed_bitmap: Vec<u8>, // One bit per leaf index
+}
+
+impl MerkleDistributor {
+    pub fn space(num_recipients: u32) -> usize {
+        8 + // discriminator
+        8 + // airdrop_id
+        32 + // root
+        8 + // total_amount
+        8 + // claimed_amount
+        4 + // num_recipients
+        4 + // num_claimed
+        8 + // expires_at
+        1 + // reclaimed
+        9 + // activity_epoch
+        4 + Self::bitmap_len(num_recipients) // claimed_bitmap
+    }
+
+    fn bitmap_len(num_recipients: u32) -> usize {
+        (num_recipients as usize).div_ceil(8)
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed_bitmap[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    fn set_claimed(&mut self, index: u32) {
+        self.claimed_bitmap[index as usize / 8] |= 1 << (index % 8);
+    }
+}
+
+pub fn airdrop_leaf(index: u32, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, &index.to_le_bytes(), claimant.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[NODE_PREFIX, &node, sibling]).to_bytes()
+        } else {
+            hashv(&[NODE_PREFIX, sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
+
+#[derive(Accounts)]
+#[instruction(airdrop_id: u64, root: [u8; 32], total_amount: u64, num_recipients: u32)]
+pub struct CreateAirdrop<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = MerkleDistributor::space(num_recipients),
+        seeds = [b"airdrop", airdrop_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub distributor: Box<Account<'info, MerkleDistributor>>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA that owns every program vault, never holds data
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_vault"],
+        bump,
+    )]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"airdrop_escrow", airdrop_id.to_le_bytes().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    // Passed for activity airdrops, the root and recipient count must match the snapshot
+    pub activity_snapshot: Option<Box<Account<'info, ActivitySnapshot>>>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    pub claimant: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop", distributor.airdrop_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub distributor: Box<Account<'info, MerkleDistributor>>,
+
+    /// CHECK: PDA that owns every program vault, never holds data
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_escrow", distributor.airdrop_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = escrow.mint,
+        token::authority = claimant,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    // Required for activity airdrops, the claimant's record for the snapshot's epoch
+    pub epoch_activity: Option<Account<'info, EpochActivity>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// Permissionless once the airdrop has expired
+#[derive(Accounts)]
+pub struct ReclaimAirdrop<'info> {
+    #[account(
+        mut,
+        seeds = [b"airdrop", distributor.airdrop_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub distributor: Box<Account<'info, MerkleDistributor>>,
+
+    /// CHECK: PDA that owns every program vault, never holds data
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_escrow", distributor.airdrop_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"emission_reserve"],
+        bump,
+    )]
+    pub emission_reserve: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"emission_ledger"],
+        bump,
+    )]
+    pub emission_ledger: Account<'info, EmissionLedger>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn create_airdrop(
+    ctx: Context<CreateAirdrop>,
+    airdrop_id: u64,
+    root: [u8; 32],
+    total_amount: u64,
+    num_recipients: u32,
+    expires_at: i64,
+) -> Result<()> {
+    require!(
+        num_recipients > 0 && num_recipients <= MAX_AIRDROP_RECIPIENTS && total_amount > 0,
+        TokenError::InvalidAirdrop
+    );
+    require!(expires_at > Clock::get()?.unix_timestamp, TokenError::InvalidAirdrop);
+    require!(
+        ctx.accounts.airdrop_vault.amount >= total_amount,
+        TokenError::InsufficientTokenBalance
+    );
+    let activity_epoch = match &ctx.accounts.activity_snapshot {
+        Some(snapshot) => {
+            require!(
+                snapshot.root == root && snapshot.recipient_count == num_recipients,
+                TokenError::InvalidSnapshot
+            );
+            Some(snapshot.epoch)
+        }
+        None => None,
+    };
+
+    let seeds = &["vault_authority".as_bytes(), &[ctx.bumps.vault_authority]];
+    let signer = [&seeds[..]];
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.airdrop_vault.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            &signer,
+        ),
+        total_amount,
+    )?;
+
+    let distributor = &mut ctx.accounts.distributor;
+    distributor.airdrop_id = airdrop_id;
+    distributor.root = root;
+    distributor.total_amount = total_amount;
+    distributor.claimed_amount = 0;
+    distributor.num_recipients = num_recipients;
+    distributor.num_claimed = 0;
+    distributor.expires_at = expires_at;
+    distributor.reclaimed = false;
+    distributor.activity_epoch = activity_epoch;
+    distributor.claimed_bitmap = vec![0; MerkleDistributor::bitmap_len(num_recipients)];
+
+    msg!("Airdrop {} created: {} tokens for {} recipients", airdrop_id, total_amount, num_recipients);
+    msg!("Expires at {}", expires_at);
+    Ok(())
+}
+
+pub fn claim_airdrop(
+    ctx: Context<ClaimAirdrop>,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Distribution)?;
+
+    let distributor = &ctx.accounts.distributor;
+    require!(Clock::get()?.unix_timestamp < distributor.expires_at, TokenError::AirdropExpired);
+    require!(index < distributor.num_recipients, TokenError::InvalidMerkleProof);
+    require!(!distributor.is_claimed(index), TokenError::AirdropAlreadyClaimed);
+    if let Some(epoch) = distributor.activity_epoch {
+        let epoch_activity = ctx.accounts.epoch_activity.as_ref().ok_or(TokenError::NotActiveInEpoch)?;
+        require!(
+            epoch_activity.wallet == ctx.accounts.claimant.key()
+                && epoch_activity.epoch == epoch
+                && epoch_activity.was_active(),
+            TokenError::NotActiveInEpoch
+        );
+    }
+
+    let leaf = airdrop_leaf(index, &ctx.accounts.claimant.key(), amount);
+    require!(
+        verify_merkle_proof(&proof, &distributor.root, leaf),
+        TokenError::InvalidMerkleProof
+    );
+
+    // A bad root can't drain more than was escrowed
+    let claimed_amount = distributor.claimed_amount
+        .checked_add(amount)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    require!(claimed_amount <= distributor.total_amount, TokenError::InvalidAirdrop);
+
+    let seeds = &["vault_authority".as_bytes(), &[ctx.bumps.vault_authority]];
+    let signer = [&seeds[..]];
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            &signer,
+        ),
+        amount,
+    )?;
+
+    let distributor = &mut ctx.accounts.distributor;
+    distributor.set_claimed(index);
+    distributor.claimed_amount = claimed_amount;
+    distributor.num_claimed += 1;
+
+    msg!("Airdrop {}: leaf {} claimed {} tokens", distributor.airdrop_id, index, amount);
+    Ok(())
+}
+
+pub fn reclaim_airdrop(ctx: Context<ReclaimAirdrop>) -> Result<()> {
+    let distributor = &ctx.accounts.distributor;
+    require!(Clock::get()?.unix_timestamp >= distributor.expires_at, TokenError::AirdropNotExpired);
+    require!(!distributor.reclaimed, TokenError::AirdropAlreadyReclaimed);
+
+    let amount = ctx.accounts.escrow.amount;
+    if amount > 0 {
+        let seeds = &["vault_authority".as_bytes(), &[ctx.bumps.vault_authority]];
+        let signer = [&seeds[..]];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.emission_reserve.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                &signer,
+            ),
+            amount,
+        )?;
+    }
+
+    ctx.accounts.emission_ledger.record_return(amount)?;
+    ctx.accounts.distributor.reclaimed = true;
+
+    msg!("Airdrop {} expired, returned {} tokens to the emission reserve", ctx.accounts.distributor.airdrop_id, amount);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[NODE_PREFIX, a, b]).to_bytes()
+        } else {
+            hashv(&[NODE_PREFIX, b, a]).to_bytes()
+        }
+    }
+
+    // Four-leaf tree: returns the leaves, claimants and root
+    fn tree() -> ([[u8; 32]; 4], [Pubkey; 4], [u8; 32]) {
+        let claimants = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let leaves = [
+            airdrop_leaf(0, &claimants[0], 100),
+            airdrop_leaf(1, &claimants[1], 200),
+            airdrop_leaf(2, &claimants[2], 300),
+            airdrop_leaf(3, &claimants[3], 400),
+        ];
+        let root = node(&node(&leaves[0], &leaves[1]), &node(&leaves[2], &leaves[3]));
+        (leaves, claimants, root)
+    }
+
+    #[test]
+    fn every_leaf_verifies_against_the_root() {
+        let (leaves, _, root) = tree();
+        let left = node(&leaves[0], &leaves[1]);
+        let right = node(&leaves[2], &leaves[3]);
+
+        assert!(verify_merkle_proof(&[leaves[1], right], &root, leaves[0]));
+        assert!(verify_merkle_proof(&[leaves[0], right], &root, leaves[1]));
+        assert!(verify_merkle_proof(&[leaves[3], left], &root, leaves[2]));
+        assert!(verify_merkle_proof(&[leaves[2], left], &root, leaves[3]));
+    }
+
+    #[test]
+    fn a_changed_amount_claimant_or_index_does_not_verify() {
+        let (leaves, claimants, root) = tree();
+        let proof = [leaves[1], node(&leaves[2], &leaves[3])];
+
+        assert!(!verify_merkle_proof(&proof, &root, airdrop_leaf(0, &claimants[0], 101)));
+        assert!(!verify_merkle_proof(&proof, &root, airdrop_leaf(0, &claimants[1], 100)));
+        assert!(!verify_merkle_proof(&proof, &root, airdrop_leaf(1, &claimants[0], 100)));
+    }
+
+    #[test]
+    fn a_truncated_proof_does_not_verify() {
+        let (leaves, _, root) = tree();
+
+        assert!(!verify_merkle_proof(&[leaves[1]], &root, leaves[0]));
+        assert!(!verify_merkle_proof(&[], &root, leaves[0]));
+    }
+
+    #[test]
+    fn the_bitmap_tracks_each_index_on_its_own() {
+        let mut distributor = MerkleDistributor {
+            airdrop_id: 0,
+            root: [0; 32],
+            total_amount: 0,
+            claimed_amount: 0,
+            num_recipients: 20,
+            num_claimed: 0,
+            expires_at: 0,
+            reclaimed: false,
+            activity_epoch: None,
+            claimed_bitmap: vec![0; MerkleDistributor::bitmap_len(20)],
+        };
+        distributor.set_claimed(9);
+
+        assert_eq!(distributor.claimed_bitmap.len(), 3);
+        assert!(distributor.is_claimed(9));
+        assert!(!distributor.is_claimed(8));
+        assert!(!distributor.is_claimed(17));
+    }
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::program_config::ProgramConfig;
+use crate::instructions::token_errors::TokenError;
+
+pub const MAX_ATTESTORS: usize = 10;
+
+// Cluster the attestor set was deployed on. The program id is the same across clusters,
+// so its tag goes into every attested message
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttestationCluster {
+    Localnet,
+    Devnet,
+    Testnet,
+    MainnetBeta,
+}
+
+impl AttestationCluster {
+    pub fn tag(&self) -> &'static [u8] {
+        match self {
+            AttestationCluster::Localnet => b"localnet",
+            AttestationCluster::Devnet => b"devnet",
+            AttestationCluster::Testnet => b"testnet",
+            AttestationCluster::MainnetBeta => b"mainnet-beta",
+        }
+    }
+}
+
+// Keys allowed to attest off-chain reports, and how many must sign each report
+#[account]
+pub struct AttestorSet {
+    pub attestors: Vec<Pubkey>,
+    pub threshold: u8,
+    pub report_count: u64,
+    pub cluster: AttestationCluster, // Set once at initialization
+}
+
+impl AttestorSet {
+    pub const SPACE: usize = 8 + // discriminator
+        4 + (32 * MAX_ATTESTORS) + // attestors
+        1 + // threshold
+        8 + // report_count
+        1; // cluster
+}
+
+#[derive(Accounts)]
+pub struct InitializeAttestorSet<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = AttestorSet::SPACE,
+        seeds = [b"attestor_set"],
+        bump
+    )]
+    pub attestor_set: Account<'info, AttestorSet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAttestorSet<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"attestor_set"],
+        bump,
+    )]
+    pub attestor_set: Account<'info, AttestorSet>,
+}
+
+fn validate_attestors(attestors: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !attestors.is_empty() && attestors.len() <= MAX_ATTESTORS,
+        TokenError::InvalidAttestorSet
+    );
+    require!(
+        threshold > 0 && threshold as usize <= attestors.len(),
+        TokenError::InvalidAttestorSet
+    );
+    for (i, attestor) in attestors.iter().enumerate() {
+        require!(
+            !attestors[..i].contains(attestor),
+            TokenError::InvalidAttestorSet
+        );
+    }
+    Ok(())
+}
+
+pub fn initialize_attestor_set(
+    ctx: Context<InitializeAttestorSet>,
+    attestors: Vec<Pubkey>,
+    threshold: u8,
+    cluster: AttestationCluster,
+) -> Result<()> {
+    validate_attestors(&attestors, threshold)?;
+
+    let attestor_set = &mut ctx.accounts.attestor_set;
+    attestor_set.attestors = attestors;
+    attestor_set.threshold = threshold;
+    attestor_set.report_count = 0;
+    attestor_set.cluster = cluster;
+
+    msg!("Attestor set initialized with threshold {} on {:?}", threshold, cluster);
+    Ok(())
+}
+
+pub fn update_attestor_set(
+    ctx: Context<UpdateAttestorSet>,
+    attestors: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    validate_attestors(&attestors, threshold)?;
+
+    let attestor_set = &mut ctx.accounts.attestor_set;
+    attestor_set.attestors = attestors;
+    attestor_set.threshold = threshold;
+
+    msg!("Attestor set updated with threshold {}", threshold);
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::program_config::ProgramConfig;
+use crate::instructions::token_constants::{FOUNDER_ALLOCATION, DEV_ALLOCATION, AFFILIATOR_ALLOCATION};
+use crate::instructions::token_errors::TokenError;
+
+/*
+    allocation categories and the wallet each one pays out to
+    wallets are rotated in two steps: the admin proposes a new wallet, and the
+    rotation can only be executed once BENEFICIARY_ROTATION_DELAY has passed,
+    so holders see every change coming
+*/
+
+pub const BENEFICIARY_ROTATION_DELAY: i64 = 604_800; // 60 * 60 * 24 * 7, one week
+pub const ALLOCATION_CATEGORY_COUNT: usize = 3;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AllocationCategory {
+    Founder,
+    Dev,
+    Marketing,
+}
+
+impl AllocationCategory {
+    // Marketing is paid out of the affiliator rewards allocation
+    pub fn allocation(&self) -> u64 {
+        match self {
+            AllocationCategory::Founder => FOUNDER_ALLOCATION,
+            AllocationCategory::Dev => DEV_ALLOCATION,
+            AllocationCategory::Marketing => AFFILIATOR_ALLOCATION,
+        }
+    }
+
+    // Founder and dev only pay out through their vesting schedules
+    pub fn is_vested(&self) -> bool {
+        matches!(self, AllocationCategory::Founder | AllocationCategory::Dev)
+    }
+
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            AllocationCategory::Founder => b"founder",
+            AllocationCategory::Dev => b"dev",
+            AllocationCategory::Marketing => b"marketing",
+        }
+    }
+
+    pub fn vault_seed(&self) -> &'static [u8] {
+        match self {
+            AllocationCategory::Founder => b"founder_vault",
+            AllocationCategory::Dev => b"dev_vault",
+            AllocationCategory::Marketing => b"affiliator_vault",
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PendingRotation {
+    pub category: AllocationCategory,
+    pub new_wallet: Pubkey,
+    pub eligible_at: i64,
+}
+
+impl PendingRotation {
+    pub const SPACE: usize = 1 + // category
+        32 + // new_wallet
+        8; // eligible_at
+}
+
+#[account]
+pub struct BeneficiaryRegistry {
+    pub founder: Pubkey,
+    pub dev: Pubkey,
+    pub marketing: Pubkey,
+    pub pending_rotations: Vec<PendingRotation>, // At most one per category
+}
+
+impl BeneficiaryRegistry {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // founder
+        32 + // dev
+        32 + // marketing
+        4 + (PendingRotation::SPACE * ALLOCATION_CATEGORY_COUNT); // pending_rotations
+
+    pub fn wallet_for(&self, category: AllocationCategory) -> Pubkey {
+        match category {
+            AllocationCategory::Founder => self.founder,
+            AllocationCategory::Dev => self.dev,
+            AllocationCategory::Marketing => self.marketing,
+        }
+    }
+
+    fn set_wallet(&mut self, category: AllocationCategory, wallet: Pubkey) {
+        match category {
+            AllocationCategory::Founder => self.founder = wallet,
+            AllocationCategory::Dev => self.dev = wallet,
+            AllocationCategory::Marketing => self.marketing = wallet,
+        }
+    }
+
+    fn pending_index(&self, category: AllocationCategory) -> Option<usize> {
+        self.pending_rotations.iter().position(|r| r.category == category)
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeBeneficiaryRegistry<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = BeneficiaryRegistry::SPACE,
+        seeds = [b"beneficiary_registry"],
+        bump
+    )]
+    pub beneficiary_registry: Account<'info, BeneficiaryRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GovernBeneficiaryRotation<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"beneficiary_registry"],
+        bump,
+    )]
+    pub beneficiary_registry: Account<'info, BeneficiaryRegistry>,
+}
+
+// Permissionless once the delay has passed
+#[derive(Accounts)]
+pub struct ExecuteBeneficiaryRotation<'info> {
+    #[account(
+        mut,
+        seeds = [b"beneficiary_registry"],
+        bump,
+    )]
+    pub beneficiary_registry: Account<'info, BeneficiaryRegistry>,
+}
+
+pub fn initialize_beneficiary_registry(
+    ctx: Context<InitializeBeneficiaryRegistry>,
+    founder: Pubkey,
+    dev: Pubkey,
+    marketing: Pubkey,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.beneficiary_registry;
+    registry.founder = founder;
+    registry.dev = dev;
+    registry.marketing = marketing;
+    registry.pending_rotations = Vec::new();
+
+    msg!("Beneficiary registry initialized");
+    Ok(())
+}
+
+pub fn propose_beneficiary_rotation(
+    ctx: Context<GovernBeneficiaryRotation>,
+    category: AllocationCategory,
+    new_wallet: Pubkey,
+) -> Result<()> {
+    require!(new_wallet != Pubkey::default(), TokenError::InvalidBeneficiary);
+
+    let eligible_at = Clock::get()?.unix_timestamp
+        .checked_add(BENEFICIARY_ROTATION_DELAY)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    let rotation = PendingRotation { category, new_wallet, eligible_at };
+
+    // A new proposal replaces an earlier one for the same category and restarts the delay
+    let registry = &mut ctx.accounts.beneficiary_registry;
+    match registry.pending_index(category) {
+        Some(index) => registry.pending_rotations[index] = rotation,
+        None => registry.pending_rotations.push(rotation),
+    }
+
+    msg!("Proposed {:?} beneficiary {}, eligible at {}", category, new_wallet, eligible_at);
+    Ok(())
+}
+
+pub fn cancel_beneficiary_rotation(
+    ctx: Context<GovernBeneficiaryRotation>,
+    category: AllocationCategory,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.beneficiary_registry;
+    let index = registry.pending_index(category).ok_or(TokenError::NoPendingRotation)?;
+    registry.pending_rotations.remove(index);
+
+    msg!("Cancelled pending {:?} beneficiary rotation", category);
+    Ok(())
+}
+
+pub fn execute_beneficiary_rotation(
+    ctx: Context<ExecuteBeneficiaryRotation>,
+    category: AllocationCategory,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.beneficiary_registry;
+    let index = registry.pending_index(category).ok_or(TokenError::NoPendingRotation)?;
+    require!(
+        Clock::get()?.unix_timestamp >= registry.pending_rotations[index].eligible_at,
+        TokenError::RotationNotReady
+    );
+
+    let rotation = registry.pending_rotations.remove(index);
+    registry.set_wallet(category, rotation.new_wallet);
+
+    msg!("{:?} beneficiary rotated to {}", category, rotation.new_wallet);
+    Ok(())
+}
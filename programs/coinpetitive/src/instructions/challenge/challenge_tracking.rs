@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::instructions::challenge::errors::ErrorCode;
 
 #[account]
 pub struct ChallengeTracker {
@@ -40,6 +41,20 @@ pub struct TrackChallenge<'info> {
     pub challenge_tracker: Account<'info, ChallengeTracker>,
 }
 
+// Hands the tracker to a new authority, e.g. the multisig signer PDA
+#[derive(Accounts)]
+pub struct SetChallengeTrackerAuthority<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"challenge_tracker"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub challenge_tracker: Account<'info, ChallengeTracker>,
+}
+
 pub fn initialize_challenge_tracker(ctx: Context<InitializeChallengeTracker>) -> Result<()> {
     let tracker = &mut ctx.accounts.challenge_tracker;
     tracker.total_challenges = 0;
@@ -50,6 +65,17 @@ pub fn initialize_challenge_tracker(ctx: Context<InitializeChallengeTracker>) ->
     Ok(())
 }
 
+pub fn set_challenge_tracker_authority(
+    ctx: Context<SetChallengeTrackerAuthority>,
+    new_authority: Pubkey
+) -> Result<()> {
+    ctx.accounts.challenge_tracker.authority = new_authority;
+    
+    msg!("Challenge tracker authority set to {}", new_authority);
+    
+    Ok(())
+}
+
 pub fn track_challenge_completion(ctx: Context<TrackChallenge>) -> Result<()> {
     let tracker = &mut ctx.accounts.challenge_tracker;
     
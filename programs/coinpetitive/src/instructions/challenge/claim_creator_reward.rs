@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program;
+use anchor_lang::solana_program::{self, system_instruction};
+use anchor_spl::token_interface::Mint;
 use crate::instructions::challenge::types::Challenge;
 use crate::instructions::challenge::errors::ErrorCode;
-
-pub const TOKEN_2022_PROGRAM_ID_STR: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+use crate::instructions::challenge::token_transfer::transfer_cpt;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
+use crate::instructions::revenue_ledger::{RevenueLedger, RevenueSource};
 
 #[derive(Accounts)]
 pub struct ClaimCreatorReward<'info> {
@@ -11,6 +13,12 @@ pub struct ClaimCreatorReward<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
     
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
     // The challenge account - verify it's finalized and creator is correct
     #[account(
         mut,
@@ -20,10 +28,14 @@ pub struct ClaimCreatorReward<'info> {
     pub challenge: Account<'info, Challenge>,
     
     // Token accounts
-    /// CHECK: Token-2022 program
-    #[account(address = TOKEN_2022_PROGRAM_ID_STR.parse::<Pubkey>().unwrap())]
+    /// CHECK: Token program from the program config
+    #[account(address = program_config.token_program @ ErrorCode::InvalidTokenProgram)]
     pub token_program: AccountInfo<'info>,
     
+    // CPT mint from the program config, TransferChecked needs its decimals
+    #[account(address = program_config.cpt_mint @ ErrorCode::InvalidTokenMint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    
     /// CHECK: Main treasury PDA - verified in handler
     #[account(mut)]
     pub treasury: AccountInfo<'info>,
@@ -35,12 +47,30 @@ pub struct ClaimCreatorReward<'info> {
     /// CHECK: Creator's token account
     #[account(mut)]
     pub creator_token_account: AccountInfo<'info>,
+
+    /// CHECK: Revenue treasury PDA that receives the platform fee, holds lamports only
+    #[account(
+        mut,
+        seeds = [b"revenue_treasury"],
+        bump,
+    )]
+    pub revenue_treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue_ledger"],
+        bump,
+    )]
+    pub revenue_ledger: Box<Account<'info, RevenueLedger>>,
     
     // System program
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle(ctx: Context<ClaimCreatorReward>) -> Result<()> {
+// The transfer hook's accounts are passed as remaining accounts
+pub fn handle<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimCreatorReward<'info>>) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Finalization)?;
+    
     let challenge = &ctx.accounts.challenge;
     
     // Verify treasury matches the one stored in the challenge
@@ -53,7 +83,6 @@ pub fn handle(ctx: Context<ClaimCreatorReward>) -> Result<()> {
     let treasury_token_account = ctx.accounts.treasury_token_account.to_account_info();
     let creator_token_account = ctx.accounts.creator_token_account.to_account_info();
     let treasury = ctx.accounts.treasury.to_account_info();
-    let token_program = ctx.accounts.token_program.to_account_info();
     
     // Get the actual treasury token balance
     let treasury_token_account_data = treasury_token_account.try_borrow_data()?;
@@ -90,30 +119,37 @@ pub fn handle(ctx: Context<ClaimCreatorReward>) -> Result<()> {
     // Transfer all remaining tokens to creator
     msg!("Transferring remaining {} tokens from treasury to creator", actual_treasury_balance);
     
-    let creator_transfer_ix = solana_program::instruction::Instruction {
-        program_id: token_program.key(),
-        accounts: vec![
-            solana_program::instruction::AccountMeta::new(treasury_token_account.key(), false),
-            solana_program::instruction::AccountMeta::new(creator_token_account.key(), false),
-            solana_program::instruction::AccountMeta::new_readonly(treasury.key(), true),
-        ],
-        data: [3].into_iter() // Token instruction 3 = Transfer
-            .chain(actual_treasury_balance.to_le_bytes().into_iter())
-            .collect(),
-    };
-    
-    // Execute the transfer - reuse the account infos we got earlier
-    solana_program::program::invoke_signed(
-        &creator_transfer_ix,
-        &[
-            treasury_token_account,
-            creator_token_account,
-            treasury,
-        ],
+    // Reuse the account infos we got earlier
+    transfer_cpt(
+        &ctx.accounts.token_program,
+        &treasury_token_account,
+        &ctx.accounts.token_mint,
+        &creator_token_account,
+        &treasury,
+        ctx.remaining_accounts,
+        actual_treasury_balance,
         &[treasury_seeds],
     )?;
     
     msg!("Transferred {} tokens to creator", actual_treasury_balance);
     
+    // Platform fee in lamports to the revenue treasury
+    let platform_fee = ctx.accounts.program_config.platform_fee;
+    msg!("Transferring {} lamports platform fee to program treasury", platform_fee);
+    let platform_fee_ix = system_instruction::transfer(
+        &ctx.accounts.creator.key(),
+        &ctx.accounts.revenue_treasury.key(),
+        platform_fee
+    );
+    solana_program::program::invoke(
+        &platform_fee_ix,
+        &[
+            ctx.accounts.creator.to_account_info(),
+            ctx.accounts.revenue_treasury.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+    ctx.accounts.revenue_ledger.record_revenue(RevenueSource::PlatformFee, platform_fee)?;
+    
     Ok(())
 }
\ No newline at end of file
@@ -1,12 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{self, system_instruction};
-use std::str::FromStr;
 use crate::instructions::challenge::types::Challenge;
 use crate::instructions::errors::ErrorCode;
-
-pub const TOKEN_2022_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
-pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
-pub const CPT_TOKEN_MINT: &str = "mntjJeXswzxFCnCY1Zs2ekEzDvBVaVdyTVFXbBHfmo9";
+use crate::instructions::revenue_ledger::{RevenueLedger, RevenueSource};
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
 
 #[derive(Accounts)]
 #[instruction(
@@ -20,6 +17,12 @@ pub struct CreateChallenge<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
     #[account(
         init,
         payer = user,
@@ -41,7 +44,9 @@ pub struct CreateChallenge<'info> {
                4 + (40 * 20) + // submission_votes: Vec<(Pubkey, u64)> - limit to 20 submissions
                4 + (64 * 50) + // voters: Vec<(Pubkey, Pubkey)> - limit to 50 voters
                32 + // treasury: Pubkey
-               32   // voting_treasury_pda: Pubkey
+               32 + // voting_treasury_pda: Pubkey
+               4 + (64 * 20) + // submitters: Vec<(Pubkey, Pubkey)> - same 20 submissions
+               33   // winning_submission: Option<Pubkey>
     )]
     pub challenge: Account<'info, Challenge>,
     
@@ -50,16 +55,29 @@ pub struct CreateChallenge<'info> {
     #[account(mut)]
     pub treasury: AccountInfo<'info>,
     
-    /// CHECK: This is the program account that will receive the creation fee
-    #[account(mut)]
+    /// CHECK: Revenue treasury PDA that receives the creation fee, holds lamports only
+    #[account(
+        mut,
+        seeds = [b"revenue_treasury"],
+        bump,
+    )]
     pub program_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue_ledger"],
+        bump,
+    )]
+    pub revenue_ledger: Box<Account<'info, RevenueLedger>>,
     
     pub system_program: Program<'info, System>,
     
-    /// CHECK: Token-2022 program
+    /// CHECK: Token program from the program config
+    #[account(address = program_config.token_program @ ErrorCode::InvalidTokenProgram)]
     pub token_program: AccountInfo<'info>,
     
-    /// CHECK: Token mint using Token-2022 
+    /// CHECK: CPT mint from the program config
+    #[account(address = program_config.cpt_mint @ ErrorCode::InvalidTokenMint)]
     pub token_mint: AccountInfo<'info>,
     
     /// CHECK: Creator's token account
@@ -70,6 +88,7 @@ pub struct CreateChallenge<'info> {
     pub treasury_token_account: AccountInfo<'info>,
 
     /// CHECK: Associated Token Program
+    #[account(address = anchor_spl::associated_token::ID)]
     pub associated_token_program: AccountInfo<'info>,
 
     /// CHECK: Voting Treasury account (PDA)
@@ -87,8 +106,10 @@ pub fn handle(
     participation_fee: u64,
     voting_fee: u64,
     max_participants: u8,
-    challenge_id: u64
+    _challenge_id: u64
 ) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::ChallengeCreation)?;
+    
     // Create the treasury PDA ourselves rather than relying on the derived account
     let (treasury_pda, treasury_bump) = Pubkey::find_program_address(
         &[b"treasury", ctx.accounts.challenge.key().as_ref()],
@@ -153,14 +174,14 @@ pub fn handle(
         &[&[b"voting_treasury", ctx.accounts.challenge.key().as_ref(), &[voting_treasury_bump]]],
     )?;
 
-    // Define gas amount for both treasuries
-    let gas_amount = 2_000_000; // 0.002 SOL
+    // Gas deposit for both treasuries
+    let gas_amount = ctx.accounts.program_config.gas_deposit;
 
     // Transfer gas SOL to voting treasury PDA
     let voting_treasury_gas_ix = system_instruction::transfer(
         &ctx.accounts.user.key(),
         &voting_treasury_pda,
-        gas_amount // Same 0.001 SOL
+        gas_amount
     );
 
     solana_program::program::invoke(
@@ -172,8 +193,7 @@ pub fn handle(
         ],
     )?;
 
-    // Fixed creation fee of 0.002 SOL
-    let creation_fee = 2_000_000; // 0.002 SOL in lamports
+    let creation_fee = ctx.accounts.program_config.creation_fee;
     
     // Transfer SOL creation fee to program treasury
     msg!("Transferring {} lamports to program treasury", creation_fee);
@@ -191,8 +211,9 @@ pub fn handle(
             ctx.accounts.system_program.to_account_info(),
         ],
     )?;
+    ctx.accounts.revenue_ledger.record_revenue(RevenueSource::CreationFee, creation_fee)?;
     
-    // Transfer gas SOL to treasury PDA (0.001 SOL for operations)
+    // Transfer gas SOL to treasury PDA for operations
     msg!("Transferring gas SOL to treasury PDA");
     let treasury_gas_ix = system_instruction::transfer(
         &ctx.accounts.user.key(),
@@ -209,17 +230,6 @@ pub fn handle(
         ],
     )?;
     
-    // Verify token program is the expected one
-    let token_2022_id = Pubkey::from_str(TOKEN_2022_PROGRAM_ID).unwrap();
-    require!(
-        ctx.accounts.token_program.key() == token_2022_id,
-        ErrorCode::InvalidTokenProgram
-    );
-    
-    // Verify token mint is the expected one
-    let token_mint_id = Pubkey::from_str(CPT_TOKEN_MINT).unwrap();
-
-    
     // Create ATA for treasury using proper ATA instruction
     msg!("Creating associated token account for treasury");
     
@@ -298,6 +308,8 @@ pub fn handle(
     challenge.participants = Vec::new();
     challenge.submission_votes = Vec::new();
     challenge.voters = Vec::new();
+    challenge.submitters = Vec::new();
+    challenge.winning_submission = None;
     
     // Store the treasury address in the challenge
     challenge.treasury = treasury_pda;
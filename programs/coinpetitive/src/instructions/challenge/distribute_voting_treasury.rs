@@ -1,17 +1,21 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program;
+use anchor_spl::token_interface::Mint;
 use crate::instructions::challenge::types::Challenge;
 use crate::instructions::challenge::errors::ErrorCode;
-
-// Constants for clarity
-pub const TOKEN_2022_PROGRAM_ID_STR: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
-pub const CPT_TOKEN_MINT: &str = "mntjJeXswzxFCnCY1Zs2ekEzDvBVaVdyTVFXbBHfmo9";
+use crate::instructions::challenge::token_transfer::transfer_cpt;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
 
 #[derive(Accounts)]
 pub struct DistributeVotingTreasury<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
     #[account(
         mut,
         // Remove this constraint to allow anyone to distribute rewards:
@@ -20,10 +24,14 @@ pub struct DistributeVotingTreasury<'info> {
     pub challenge: Account<'info, Challenge>,
     
     // Token accounts
-    /// CHECK: Token-2022 program
-    #[account(address = TOKEN_2022_PROGRAM_ID_STR.parse::<Pubkey>().unwrap())]
+    /// CHECK: Token program from the program config
+    #[account(address = program_config.token_program @ ErrorCode::InvalidTokenProgram)]
     pub token_program: AccountInfo<'info>,
     
+    // CPT mint from the program config, TransferChecked needs its decimals
+    #[account(address = program_config.cpt_mint @ ErrorCode::InvalidTokenMint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    
     /// CHECK: Voting Treasury PDA - verified in handler
     #[account(mut)]
     pub voting_treasury: AccountInfo<'info>,
@@ -37,7 +45,14 @@ pub struct DistributeVotingTreasury<'info> {
     pub voter_token_account: AccountInfo<'info>,
 }
 
-pub fn handle(ctx: Context<DistributeVotingTreasury>, voter: Pubkey, winning_voters_count: u64) -> Result<()> {
+// The transfer hook's accounts are passed as remaining accounts
+pub fn handle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeVotingTreasury<'info>>,
+    voter: Pubkey,
+    winning_voters_count: u64,
+) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Finalization)?;
+    
     let challenge = &ctx.accounts.challenge;
     
     // Verify voting treasury matches the one stored in the challenge
@@ -47,7 +62,7 @@ pub fn handle(ctx: Context<DistributeVotingTreasury>, voter: Pubkey, winning_vot
     );
     
     // Find the winning submission from the finalized challenge
-    let winning_submission = challenge.winner.ok_or(ErrorCode::ChallengeStillActive)?;
+    let winning_submission = challenge.winning_submission.ok_or(ErrorCode::ChallengeStillActive)?;
     
     // Check if this voter voted for the winning submission
     let voted_for_winner = challenge.voters
@@ -84,25 +99,14 @@ pub fn handle(ctx: Context<DistributeVotingTreasury>, voter: Pubkey, winning_vot
     // Verify that the voter_token_account belongs to the voter
     // In production this would need more verification
     
-    let voter_transfer_ix = solana_program::instruction::Instruction {
-        program_id: ctx.accounts.token_program.key(),
-        accounts: vec![
-            solana_program::instruction::AccountMeta::new(ctx.accounts.voting_treasury_token_account.key(), false),
-            solana_program::instruction::AccountMeta::new(ctx.accounts.voter_token_account.key(), false),
-            solana_program::instruction::AccountMeta::new_readonly(ctx.accounts.voting_treasury.key(), true),
-        ],
-        data: [3].into_iter() // Token instruction 3 = Transfer
-            .chain(reward_per_voter.to_le_bytes().into_iter())
-            .collect(),
-    };
-    
-    solana_program::program::invoke_signed(
-        &voter_transfer_ix,
-        &[
-            ctx.accounts.voting_treasury_token_account.to_account_info(),
-            ctx.accounts.voter_token_account.to_account_info(),
-            ctx.accounts.voting_treasury.to_account_info(),
-        ],
+    transfer_cpt(
+        &ctx.accounts.token_program,
+        &ctx.accounts.voting_treasury_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.voter_token_account,
+        &ctx.accounts.voting_treasury,
+        ctx.remaining_accounts,
+        reward_per_voter,
         &[voting_treasury_seeds],
     )?;
     
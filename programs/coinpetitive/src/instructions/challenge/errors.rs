@@ -53,7 +53,9 @@ pub enum ErrorCode {
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
     #[msg("Insufficient funds")]
-    InsufficientFunds
+    InsufficientFunds,
+    #[msg("Referral code and referrer accounts must be passed together")]
+    InvalidReferral
 
 
 }
\ No newline at end of file
@@ -1,16 +1,26 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program;
+use anchor_lang::solana_program::{self, system_instruction};
+use anchor_spl::token_interface::Mint;
 use crate::instructions::challenge::types::Challenge;
 use crate::instructions::challenge::errors::ErrorCode;
+use crate::instructions::challenge::token_transfer::transfer_cpt;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
+use crate::instructions::revenue_ledger::{RevenueLedger, RevenueSource};
 use crate::instructions::challenge::challenge_tracking::ChallengeTracker;
-
-pub const TOKEN_2022_PROGRAM_ID_STR: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+use crate::instructions::token::TokenState;
+use crate::instructions::user_stats::{Activity, ActivityEpoch, EpochActivity, UserStats};
 
 #[derive(Accounts)]
 pub struct FinalizeChallenge<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
     #[account(
         mut,
         constraint = challenge.is_active @ ErrorCode::ChallengeNotActive
@@ -18,10 +28,14 @@ pub struct FinalizeChallenge<'info> {
     pub challenge: Account<'info, Challenge>,
     
     // Token accounts
-    /// CHECK: Token-2022 program
-    #[account(address = TOKEN_2022_PROGRAM_ID_STR.parse::<Pubkey>().unwrap())]
+    /// CHECK: Token program from the program config
+    #[account(address = program_config.token_program @ ErrorCode::InvalidTokenProgram)]
     pub token_program: AccountInfo<'info>,
     
+    // CPT mint from the program config, TransferChecked needs its decimals
+    #[account(address = program_config.cpt_mint @ ErrorCode::InvalidTokenMint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    
     /// CHECK: Winner's token account - verified in handler
     #[account(mut)]
     pub winner_token_account: AccountInfo<'info>,
@@ -47,12 +61,66 @@ pub struct FinalizeChallenge<'info> {
         bump,
     )]
     pub challenge_tracker: Account<'info, ChallengeTracker>,
+
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    #[account(
+        mut,
+        seeds = [b"activity_epoch"],
+        bump,
+    )]
+    pub activity_epoch: Box<Account<'info, ActivityEpoch>>,
+
+    /// CHECK: The winning wallet, verified against the winning submission's submitter in handler
+    pub winner: AccountInfo<'info>,
+
+    // Stats of the winning wallet, created here if it never paid a participation fee
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserStats::SPACE,
+        seeds = [b"user_stats", winner.key().as_ref()],
+        bump
+    )]
+    pub winner_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = EpochActivity::SPACE,
+        seeds = [b"epoch_activity", activity_epoch.epoch.to_le_bytes().as_ref(), winner.key().as_ref()],
+        bump
+    )]
+    pub winner_epoch_activity: Box<Account<'info, EpochActivity>>,
+
+    /// CHECK: Revenue treasury PDA that receives the platform fee, holds lamports only
+    #[account(
+        mut,
+        seeds = [b"revenue_treasury"],
+        bump,
+    )]
+    pub revenue_treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue_ledger"],
+        bump,
+    )]
+    pub revenue_ledger: Box<Account<'info, RevenueLedger>>,
     
     // System program
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle(ctx: Context<FinalizeChallenge>) -> Result<()> {
+// The transfer hook's accounts are passed as remaining accounts
+pub fn handle<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeChallenge<'info>>) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Finalization)?;
+    
     let challenge = &mut ctx.accounts.challenge;
     
     // Verify creator key matches challenge creator
@@ -74,7 +142,7 @@ pub fn handle(ctx: Context<FinalizeChallenge>) -> Result<()> {
     
     // Sort submissions by vote count (descending)
     let mut sorted_submissions = challenge.submission_votes.clone();
-    sorted_submissions.sort_by(|a, b| b.1.cmp(&a.1));
+    sorted_submissions.sort_by_key(|s| std::cmp::Reverse(s.1));
     
     // Get the winning submission
     let (winning_submission, winning_votes) = sorted_submissions[0];
@@ -86,16 +154,21 @@ pub fn handle(ctx: Context<FinalizeChallenge>) -> Result<()> {
     
     msg!("Found winner submission: {} with {} votes", winning_submission, winning_votes);
     
-    // Find the winner's pubkey by checking which participant submitted this
-    // In our simplified implementation, we'll use the submission_id as the winner's wallet address
-    // This assumes the submission_id is the participant's pubkey
-    let winner_pubkey = winning_submission;
+    // The winner is the wallet that submitted the winning video
+    let winner_pubkey = challenge
+        .submitter_of(&winning_submission)
+        .ok_or(ErrorCode::SubmissionNotFound)?;
+    require!(
+        ctx.accounts.winner.key() == winner_pubkey,
+        ErrorCode::InvalidWinner
+    );
     
     // Mark challenge as inactive
     challenge.is_active = false;
     
     // Set winner info
     challenge.winner = Some(winner_pubkey);
+    challenge.winning_submission = Some(winning_submission);
     challenge.winning_votes = winning_votes;
     
     // Distribute tokens: Winner gets the specified reward from main treasury
@@ -122,25 +195,14 @@ pub fn handle(ctx: Context<FinalizeChallenge>) -> Result<()> {
         // This needs to be the associated token account for the CPT token
         // In production this would need more verification
         
-        let winner_transfer_ix = solana_program::instruction::Instruction {
-            program_id: ctx.accounts.token_program.key(),
-            accounts: vec![
-                solana_program::instruction::AccountMeta::new(ctx.accounts.treasury_token_account.key(), false),
-                solana_program::instruction::AccountMeta::new(ctx.accounts.winner_token_account.key(), false),
-                solana_program::instruction::AccountMeta::new_readonly(ctx.accounts.treasury.key(), true),
-            ],
-            data: [3].into_iter() // Token instruction 3 = Transfer
-                .chain(winner_reward.to_le_bytes().into_iter())
-                .collect(),
-        };
-        
-        solana_program::program::invoke_signed(
-            &winner_transfer_ix,
-            &[
-                ctx.accounts.treasury_token_account.to_account_info(),
-                ctx.accounts.winner_token_account.to_account_info(),
-                ctx.accounts.treasury.to_account_info(),
-            ],
+        transfer_cpt(
+            &ctx.accounts.token_program,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.winner_token_account,
+            &ctx.accounts.treasury,
+            ctx.remaining_accounts,
+            winner_reward,
             &[treasury_seeds],
         )?;
         
@@ -157,33 +219,38 @@ pub fn handle(ctx: Context<FinalizeChallenge>) -> Result<()> {
         // Get creator's token account - this needs to be passed as a parameter
         // In a full implementation, we'd check that this account belongs to the creator
         
-        // instruction to transfer the remaining tokens to creator
-        let creator_transfer_ix = solana_program::instruction::Instruction {
-            program_id: ctx.accounts.token_program.key(),
-            accounts: vec![
-                solana_program::instruction::AccountMeta::new(ctx.accounts.treasury_token_account.key(), false),
-                solana_program::instruction::AccountMeta::new(ctx.accounts.creator_token_account.key(), false),
-                solana_program::instruction::AccountMeta::new_readonly(ctx.accounts.treasury.key(), true),
-            ],
-            data: [3].into_iter() // Token instruction 3 = Transfer
-                .chain(treasury_balance.to_le_bytes().into_iter())
-                .collect(),
-        };
-        
-        // Execute the transfer
-        solana_program::program::invoke_signed(
-            &creator_transfer_ix,
-            &[
-                ctx.accounts.treasury_token_account.to_account_info(),
-                ctx.accounts.creator_token_account.to_account_info(),
-                ctx.accounts.treasury.to_account_info(),
-            ],
+        transfer_cpt(
+            &ctx.accounts.token_program,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.creator_token_account,
+            &ctx.accounts.treasury,
+            ctx.remaining_accounts,
+            treasury_balance,
             &[treasury_seeds],
         )?;
         
         msg!("Transferred remaining {} tokens to creator", treasury_balance);
     }
     
+    // Platform fee in lamports to the revenue treasury
+    let platform_fee = ctx.accounts.program_config.platform_fee;
+    msg!("Transferring {} lamports platform fee to program treasury", platform_fee);
+    let platform_fee_ix = system_instruction::transfer(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.revenue_treasury.key(),
+        platform_fee
+    );
+    solana_program::program::invoke(
+        &platform_fee_ix,
+        &[
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.revenue_treasury.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+    ctx.accounts.revenue_ledger.record_revenue(RevenueSource::PlatformFee, platform_fee)?;
+    
     // Update challenge tracker with this finalized challenge
     let tracker = &mut ctx.accounts.challenge_tracker;
     tracker.total_challenges = tracker.total_challenges.checked_add(1)
@@ -192,6 +259,20 @@ pub fn handle(ctx: Context<FinalizeChallenge>) -> Result<()> {
     msg!("Challenge completed and tracked! Total challenges finalized: {}", 
         tracker.total_challenges);
     
+    // Feed the challenge milestones in the token state
+    if challenge.counts_toward_milestones() {
+        ctx.accounts.token_state.record_challenge_completed(Clock::get()?.unix_timestamp)?;
+    } else {
+        msg!(
+            "Challenge not counted towards milestones: {} participants, {} voters besides the creator",
+            challenge.counted_participants(),
+            challenge.counted_voters()
+        );
+    }
+    
+    ctx.accounts.winner_stats.record(winner_pubkey, Activity::Win, &mut ctx.accounts.activity_epoch)?;
+    ctx.accounts.winner_epoch_activity.record(winner_pubkey, ctx.accounts.activity_epoch.epoch, Activity::Win)?;
+    
     msg!("Challenge finalized successfully!");
     Ok(())
 }
\ No newline at end of file
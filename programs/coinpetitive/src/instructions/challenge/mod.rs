@@ -7,17 +7,47 @@ pub mod vote_for_submission;
 pub mod finalize_challenge;
 pub mod distribute_voting_treasury;
 pub mod claim_creator_reward;
+pub mod token_transfer;
 use anchor_lang::prelude::*;
 pub use types::*;
-pub use errors::*;
-pub use create_challenge::*;
-pub use pay_participation_fee::*;
-pub use submit_video::*;
-pub use vote_for_submission::*;
-pub use finalize_challenge::*;
-pub use distribute_voting_treasury::*;
-pub use claim_creator_reward::*;
+// Every handler module exports a `handle`, so only the accounts structs are re-exported,
+// along with the client modules #[derive(Accounts)] generates for #[program]
+pub use create_challenge::CreateChallenge;
+pub(crate) use create_challenge::__client_accounts_create_challenge;
+#[cfg(feature = "cpi")]
+pub(crate) use create_challenge::__cpi_client_accounts_create_challenge;
+pub use pay_participation_fee::PayParticipationFee;
+pub(crate) use pay_participation_fee::__client_accounts_pay_participation_fee;
+#[cfg(feature = "cpi")]
+pub(crate) use pay_participation_fee::__cpi_client_accounts_pay_participation_fee;
+pub use submit_video::SubmitVideo;
+pub(crate) use submit_video::__client_accounts_submit_video;
+#[cfg(feature = "cpi")]
+pub(crate) use submit_video::__cpi_client_accounts_submit_video;
+pub use vote_for_submission::VoteForSubmission;
+pub(crate) use vote_for_submission::__client_accounts_vote_for_submission;
+#[cfg(feature = "cpi")]
+pub(crate) use vote_for_submission::__cpi_client_accounts_vote_for_submission;
+pub use finalize_challenge::FinalizeChallenge;
+pub(crate) use finalize_challenge::__client_accounts_finalize_challenge;
+#[cfg(feature = "cpi")]
+pub(crate) use finalize_challenge::__cpi_client_accounts_finalize_challenge;
+pub use distribute_voting_treasury::DistributeVotingTreasury;
+pub(crate) use distribute_voting_treasury::__client_accounts_distribute_voting_treasury;
+#[cfg(feature = "cpi")]
+pub(crate) use distribute_voting_treasury::__cpi_client_accounts_distribute_voting_treasury;
+pub use claim_creator_reward::ClaimCreatorReward;
+pub(crate) use claim_creator_reward::__client_accounts_claim_creator_reward;
+#[cfg(feature = "cpi")]
+pub(crate) use claim_creator_reward::__cpi_client_accounts_claim_creator_reward;
 pub mod challenge_tracking;
+pub use challenge_tracking::{ChallengeTracker, InitializeChallengeTracker, SetChallengeTrackerAuthority};
+pub(crate) use challenge_tracking::__client_accounts_initialize_challenge_tracker;
+#[cfg(feature = "cpi")]
+pub(crate) use challenge_tracking::__cpi_client_accounts_initialize_challenge_tracker;
+pub(crate) use challenge_tracking::__client_accounts_set_challenge_tracker_authority;
+#[cfg(feature = "cpi")]
+pub(crate) use challenge_tracking::__cpi_client_accounts_set_challenge_tracker_authority;
 
 
 
@@ -33,35 +63,35 @@ pub fn create_challenge(
     create_challenge::handle(ctx, reward, participation_fee, voting_fee , max_participants , _challenge_id)
 }
 
-pub fn pay_participation_fee(ctx: Context<PayParticipationFee>) -> Result<()> {
+pub fn pay_participation_fee<'info>(ctx: Context<'_, '_, 'info, 'info, PayParticipationFee<'info>>) -> Result<()> {
     pay_participation_fee::handle(ctx)
 }
 
-pub fn submit_video(ctx: Context<SubmitVideo>, video_url: String) -> Result<()> {
+pub fn submit_video<'info>(ctx: Context<'_, '_, 'info, 'info, SubmitVideo<'info>>, video_url: String) -> Result<()> {
     submit_video::handle(ctx, video_url)
 }
 
-pub fn vote_for_submission(ctx: Context<VoteForSubmission>) -> Result<()> {
+pub fn vote_for_submission<'info>(ctx: Context<'_, '_, 'info, 'info, VoteForSubmission<'info>>) -> Result<()> {
     vote_for_submission::handle(ctx)
 }
 
-pub fn finalize_challenge(
-    ctx: Context<FinalizeChallenge>,
-    winner_pubkey: Pubkey,
-    winning_votes: u64
+pub fn finalize_challenge<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeChallenge<'info>>,
+    _winner_pubkey: Pubkey,
+    _winning_votes: u64
 ) -> Result<()> {
     finalize_challenge::handle(ctx)
 }
 
 // Add this function
-pub fn distribute_voting_treasury(
-    ctx: Context<DistributeVotingTreasury>,
+pub fn distribute_voting_treasury<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeVotingTreasury<'info>>,
     voter: Pubkey,
     voter_index: u64
 ) -> Result<()> {
     distribute_voting_treasury::handle(ctx, voter, voter_index)
 }
 
-pub fn claim_creator_reward(ctx: Context<ClaimCreatorReward>) -> Result<()> {
+pub fn claim_creator_reward<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimCreatorReward<'info>>) -> Result<()> {
     claim_creator_reward::handle(ctx)
 }
\ No newline at end of file
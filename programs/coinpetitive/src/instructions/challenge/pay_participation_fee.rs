@@ -1,37 +1,58 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program;
+use anchor_spl::token_interface::{Mint, TokenAccount};
 use crate::instructions::challenge::types::Challenge;
 use crate::instructions::challenge::errors::ErrorCode;
+use crate::instructions::challenge::token_transfer::transfer_cpt;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
 use crate::instructions::fee_tracking::FeeTracker;
-
-pub const TOKEN_2022_PROGRAM_ID_STR: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+use crate::instructions::token::TokenState;
+use crate::instructions::distribution_ledger::DistributionLedger;
+use crate::instructions::referral::{is_qualifying_participation, RefereeRecord, ReferralCode, Referrer};
+use crate::instructions::user_stats::{Activity, ActivityEpoch, EpochActivity, UserStats};
 
 #[derive(Accounts)]
 pub struct PayParticipationFee<'info> {
     #[account(mut)]
     pub participant: Signer<'info>,
     
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
     #[account(mut)]
     pub challenge: Account<'info, Challenge>,
     
     /// CHECK: Treasury account (PDA)
     #[account(
         mut,
-        // No constraint needed as we'll check programmatically in handle function
+        address = challenge.treasury @ ErrorCode::InvalidTreasury,
     )]
     pub treasury: AccountInfo<'info>,
     
-    /// CHECK: Token-2022 program
-    #[account(address = TOKEN_2022_PROGRAM_ID_STR.parse::<Pubkey>().unwrap())]
+    /// CHECK: Token program from the program config
+    #[account(address = program_config.token_program @ ErrorCode::InvalidTokenProgram)]
     pub token_program: AccountInfo<'info>,
     
-    /// CHECK: Participant's token account
-    #[account(mut)]
-    pub participant_token_account: AccountInfo<'info>,
+    // CPT mint from the program config, TransferChecked needs its decimals
+    #[account(address = program_config.cpt_mint @ ErrorCode::InvalidTokenMint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     
-    /// CHECK: Treasury's token account
-    #[account(mut)]
-    pub treasury_token_account: AccountInfo<'info>,
+    // Fees only count towards the entry fee milestones when paid in CPT
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = participant,
+    )]
+    pub participant_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = treasury,
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     
     #[account(
         mut,
@@ -39,11 +60,70 @@ pub struct PayParticipationFee<'info> {
         bump,
     )]
     pub fee_tracker: Account<'info, FeeTracker>,
+
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    // Exists once the participant has paid any participation fee
+    #[account(
+        init_if_needed,
+        payer = participant,
+        space = RefereeRecord::SPACE,
+        seeds = [b"referee", participant.key().as_ref()],
+        bump
+    )]
+    pub referee_record: Box<Account<'info, RefereeRecord>>,
+
+    // Only read on the first participation, ignored afterwards
+    pub referral_code: Option<Box<Account<'info, ReferralCode>>>,
+
+    // Must be the referrer of referral_code, checked when binding
+    #[account(mut)]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
+    #[account(
+        mut,
+        seeds = [b"distribution_ledger"],
+        bump,
+    )]
+    pub distribution_ledger: Box<Account<'info, DistributionLedger>>,
+
+    #[account(
+        mut,
+        seeds = [b"activity_epoch"],
+        bump,
+    )]
+    pub activity_epoch: Box<Account<'info, ActivityEpoch>>,
+
+    #[account(
+        init_if_needed,
+        payer = participant,
+        space = UserStats::SPACE,
+        seeds = [b"user_stats", participant.key().as_ref()],
+        bump
+    )]
+    pub participant_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = participant,
+        space = EpochActivity::SPACE,
+        seeds = [b"epoch_activity", activity_epoch.epoch.to_le_bytes().as_ref(), participant.key().as_ref()],
+        bump
+    )]
+    pub participant_epoch_activity: Box<Account<'info, EpochActivity>>,
     
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle(ctx: Context<PayParticipationFee>) -> Result<()> {
+// The transfer hook's accounts are passed as remaining accounts
+pub fn handle<'info>(ctx: Context<'_, '_, 'info, 'info, PayParticipationFee<'info>>) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Participation)?;
+    
     let challenge = &mut ctx.accounts.challenge;
     let participant_key = ctx.accounts.participant.key();
     
@@ -72,28 +152,15 @@ pub fn handle(ctx: Context<PayParticipationFee>) -> Result<()> {
     msg!("From participant: {}", participant_key);
     msg!("To treasury: {}", challenge.treasury);
     
-    // Create a Token-2022 Transfer instruction
-    let transfer_ix = solana_program::instruction::Instruction {
-        program_id: ctx.accounts.token_program.key(),
-        accounts: vec![
-            solana_program::instruction::AccountMeta::new(ctx.accounts.participant_token_account.key(), false),
-            solana_program::instruction::AccountMeta::new(ctx.accounts.treasury_token_account.key(), false),
-            solana_program::instruction::AccountMeta::new_readonly(ctx.accounts.participant.key(), true),
-        ],
-        // Token instruction 3 = Transfer, followed by amount as little-endian bytes
-        data: [3].into_iter()
-              .chain(challenge.participation_fee.to_le_bytes().into_iter())
-              .collect(),
-    };
-    
-    // Execute the transfer
-    solana_program::program::invoke(
-        &transfer_ix,
-        &[
-            ctx.accounts.participant_token_account.to_account_info(),
-            ctx.accounts.treasury_token_account.to_account_info(),
-            ctx.accounts.participant.to_account_info(),
-        ],
+    transfer_cpt(
+        &ctx.accounts.token_program,
+        &ctx.accounts.participant_token_account.to_account_info(),
+        &ctx.accounts.token_mint,
+        &ctx.accounts.treasury_token_account.to_account_info(),
+        &ctx.accounts.participant.to_account_info(),
+        ctx.remaining_accounts,
+        challenge.participation_fee,
+        &[],
     )?;
     
     // Update challenge treasury
@@ -108,6 +175,47 @@ pub fn handle(ctx: Context<PayParticipationFee>) -> Result<()> {
         .checked_add(challenge.participation_fee)
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
+    // Feed the entry fee milestones in the token state, a creator paying into
+    // their own challenge only moves tokens back to themselves
+    if participant_key != challenge.creator {
+        ctx.accounts.token_state.record_entry_fee(challenge.participation_fee)?;
+    } else {
+        msg!("Creator's own entry fee not counted towards milestones");
+    }
+    
+    // Bind the referee on their first participation paying at least the submission fee
+    // into a challenge neither they nor their referrer created, so free, token-cheap or
+    // self-run challenges can't farm referral bonuses
+    let referrer_wallet = ctx.accounts.referrer.as_ref().map(|referrer| referrer.wallet);
+    if ctx.accounts.referee_record.is_new()
+        && challenge.participation_fee >= ctx.accounts.program_config.submission_fee
+        && is_qualifying_participation(challenge.creator, participant_key, referrer_wallet)
+    {
+        let referral = match (&ctx.accounts.referral_code, &mut ctx.accounts.referrer) {
+            (Some(referral_code), Some(referrer)) => Some((&***referral_code, &mut ***referrer)),
+            (None, None) => None,
+            _ => return err!(ErrorCode::InvalidReferral),
+        };
+        ctx.accounts.referee_record.bind(
+            participant_key,
+            Clock::get()?.unix_timestamp,
+            referral,
+            &ctx.accounts.program_config,
+            &mut ctx.accounts.distribution_ledger,
+        )?;
+    }
+    
+    ctx.accounts.participant_stats.record(
+        participant_key,
+        Activity::Participation,
+        &mut ctx.accounts.activity_epoch,
+    )?;
+    ctx.accounts.participant_epoch_activity.record(
+        participant_key,
+        ctx.accounts.activity_epoch.epoch,
+        Activity::Participation,
+    )?;
+    
     msg!("Participation fee paid successfully");
     msg!("Participant {} added to challenge", participant_key);
     msg!("Total participation fees tracked: {}", fee_tracker.total_participation_fees);
@@ -1,13 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program;
+use anchor_spl::token_interface::Mint;
 use crate::instructions::challenge::types::Challenge;
 use crate::instructions::challenge::errors::ErrorCode;
-
-// Define Token-2022 program ID constant
-pub const TOKEN_2022_PROGRAM_ID_STR: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
-pub const CPT_TOKEN_MINT: &str = "mntjJeXswzxFCnCY1Zs2ekEzDvBVaVdyTVFXbBHfmo9";
-// Add fixed submission fee - 5 tokens with 9 decimals (5 * 10^9)
-pub const FIXED_SUBMISSION_FEE: u64 = 5_000_000_0;
+use crate::instructions::challenge::token_transfer::transfer_cpt;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
 
 // Token instruction enum
 #[derive(Clone, Debug)]
@@ -20,6 +16,12 @@ pub struct SubmitVideo<'info> {
     #[account(mut)]
     pub participant: Signer<'info>,
     
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
 
     pub challenge: Account<'info, Challenge>,
     
@@ -27,10 +29,14 @@ pub struct SubmitVideo<'info> {
     #[account(mut)]
     pub treasury: AccountInfo<'info>,
     
-    /// CHECK: Token-2022 program
-    #[account(address = TOKEN_2022_PROGRAM_ID_STR.parse::<Pubkey>().unwrap())]
+    /// CHECK: Token program from the program config
+    #[account(address = program_config.token_program @ ErrorCode::InvalidTokenProgram)]
     pub token_program: AccountInfo<'info>,
     
+    // CPT mint from the program config, TransferChecked needs its decimals
+    #[account(address = program_config.cpt_mint @ ErrorCode::InvalidTokenMint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    
     /// CHECK: Participant's token account
     #[account(mut)]
     pub participant_token_account: AccountInfo<'info>,
@@ -43,7 +49,10 @@ pub struct SubmitVideo<'info> {
     pub video_reference: AccountInfo<'info>,
 }
 
-pub fn handle(ctx: Context<SubmitVideo>, video_url: String) -> Result<()> {
+// The transfer hook's accounts are passed as remaining accounts
+pub fn handle<'info>(ctx: Context<'_, '_, 'info, 'info, SubmitVideo<'info>>, _video_url: String) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Participation)?;
+    
     let challenge = &mut ctx.accounts.challenge;
     
     // Verify treasury account matches the one stored in the challenge
@@ -52,42 +61,30 @@ pub fn handle(ctx: Context<SubmitVideo>, video_url: String) -> Result<()> {
         ErrorCode::InvalidTreasury
     );
     
-    // Use fixed submission fee instead of challenge.participation_fee
-    msg!("Submitting video and paying fixed fee: {} tokens", FIXED_SUBMISSION_FEE);
+    // Use the configured submission fee instead of challenge.participation_fee
+    let submission_fee = ctx.accounts.program_config.submission_fee;
+    msg!("Submitting video and paying fixed fee: {} tokens", submission_fee);
     msg!("From participant: {}", ctx.accounts.participant.key());
     msg!("To treasury: {}", ctx.accounts.treasury.key());
     
-    // Create a simplified Transfer instruction manually with FIXED_SUBMISSION_FEE
-    let ix = solana_program::instruction::Instruction {
-        program_id: ctx.accounts.token_program.key(),
-        accounts: vec![
-            solana_program::instruction::AccountMeta::new(ctx.accounts.participant_token_account.key(), false),
-            solana_program::instruction::AccountMeta::new(ctx.accounts.treasury_token_account.key(), false),
-            solana_program::instruction::AccountMeta::new_readonly(ctx.accounts.participant.key(), true),
-        ],
-        // Token instruction 3 = Transfer, followed by amount as little-endian bytes
-        data: [3].into_iter()
-              .chain(FIXED_SUBMISSION_FEE.to_le_bytes().into_iter())
-              .collect(),
-    };
-    
-    // Execute the transfer
-    solana_program::program::invoke(
-        &ix,
-        &[
-            ctx.accounts.participant_token_account.to_account_info(),
-            ctx.accounts.treasury_token_account.to_account_info(),
-            ctx.accounts.participant.to_account_info(),
-        ],
+    transfer_cpt(
+        &ctx.accounts.token_program,
+        &ctx.accounts.participant_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.participant.to_account_info(),
+        ctx.remaining_accounts,
+        submission_fee,
+        &[],
     )?;
     
     // Update challenge treasury with fixed fee instead of challenge.participation_fee
-    challenge.challenge_treasury += FIXED_SUBMISSION_FEE;
+    challenge.challenge_treasury += submission_fee;
     
-    msg!("Video submitted and fixed submission fee of {} paid successfully", FIXED_SUBMISSION_FEE);
+    msg!("Video submitted and fixed submission fee of {} paid successfully", submission_fee);
     
-    // Add video reference and initial votes
-    challenge.submission_votes.push((ctx.accounts.video_reference.key(), 0));
+    // Add video reference and initial votes, remembering who submitted it
+    challenge.add_submission(ctx.accounts.video_reference.key(), ctx.accounts.participant.key())?;
     
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::onchain::invoke_transfer_checked;
+use anchor_spl::token_interface::Mint;
+
+// Every CPT movement in a challenge goes through TransferChecked, so a Token-2022
+// CPT mint runs its transfer hook. The hook program, its extra account meta list
+// and the accounts it resolves are passed as remaining accounts; a legacy mint
+// needs none of them.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_cpt<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_transfer_checked(
+        token_program.key,
+        from.clone(),
+        mint.to_account_info(),
+        to.clone(),
+        authority.clone(),
+        hook_accounts,
+        amount,
+        mint.decimals,
+        signer_seeds,
+    )?;
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::instructions::challenge::errors::ErrorCode;
+use crate::instructions::token_constants::{MIN_COUNTED_PARTICIPANTS, MIN_COUNTED_VOTERS};
 
 #[account]
+#[derive(Default)]
 pub struct Challenge {
     pub creator: Pubkey,
     pub is_active: bool,
@@ -20,6 +22,8 @@ pub struct Challenge {
     pub voters: Vec<(Pubkey, Pubkey)>,         // (voter, submission_id)
     pub treasury: Pubkey,             // Treasury PDA address
     pub voting_treasury_pda: Pubkey,  // Add this new field for voting treasury PDA
+    pub submitters: Vec<(Pubkey, Pubkey)>,     // (submission_id, submitter wallet)
+    pub winning_submission: Option<Pubkey>,    // Submission the winner was paid for
 }
 
 impl Challenge {
@@ -33,6 +37,49 @@ impl Challenge {
         self.voters.iter().any(|(v, s)| v == voter && s == submission_id)
     }
     
+    // Participants other than the creator, each wallet can only join once
+    pub fn counted_participants(&self) -> usize {
+        self.participants.iter().filter(|p| **p != self.creator).count()
+    }
+    
+    // Distinct voters other than the creator, a voter can back several submissions
+    pub fn counted_voters(&self) -> usize {
+        let mut voters: Vec<&Pubkey> = self.voters
+            .iter()
+            .map(|(v, _)| v)
+            .filter(|v| **v != self.creator)
+            .collect();
+        voters.sort();
+        voters.dedup();
+        voters.len()
+    }
+    
+    // Only challenges with enough outside participants and voters feed the
+    // challenges completed milestones, so a creator can't farm them alone
+    pub fn counts_toward_milestones(&self) -> bool {
+        self.counted_participants() >= MIN_COUNTED_PARTICIPANTS
+            && self.counted_voters() >= MIN_COUNTED_VOTERS
+    }
+    
+    // Wallet that submitted a submission, the one paid if it wins
+    pub fn submitter_of(&self, submission_id: &Pubkey) -> Option<Pubkey> {
+        self.submitters
+            .iter()
+            .find(|(id, _)| id == submission_id)
+            .map(|(_, submitter)| *submitter)
+    }
+    
+    // Register a submission with no votes yet
+    pub fn add_submission(&mut self, submission_id: Pubkey, submitter: Pubkey) -> Result<()> {
+        require!(
+            self.submitter_of(&submission_id).is_none(),
+            ErrorCode::InvalidSubmissionId
+        );
+        self.submitters.push((submission_id, submitter));
+        self.submission_votes.push((submission_id, 0));
+        Ok(())
+    }
+    
     // Add a vote for a submission
     pub fn add_vote(&mut self, voter: Pubkey, submission_id: Pubkey) -> Result<()> {
         // Check if voter has already voted for this submission
@@ -40,23 +87,91 @@ impl Challenge {
             return Err(ErrorCode::AlreadyVoted.into());
         }
         
-        // Record the vote
-        self.voters.push((voter, submission_id));
-        
-        // Find or add submission to the votes tracking
+        // Only submissions made through submit_video can be voted for
         let submission_idx = self.submission_votes
             .iter()
-            .position(|(id, _)| *id == submission_id);
+            .position(|(id, _)| *id == submission_id)
+            .ok_or(ErrorCode::SubmissionNotFound)?;
         
-        if let Some(idx) = submission_idx {
-            // Update existing submission vote count
-            self.submission_votes[idx].1 += 1;
-        } else {
-            // Add new submission with 1 vote
-            self.submission_votes.push((submission_id, 1));
-        }
+        // Record the vote
+        self.voters.push((voter, submission_id));
+        self.submission_votes[submission_idx].1 += 1;
         
         Ok(())
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge_with(creator: Pubkey, participants: Vec<Pubkey>, voters: Vec<Pubkey>) -> Challenge {
+        let submission = Pubkey::new_unique();
+        Challenge {
+            creator,
+            participants,
+            voters: voters.into_iter().map(|v| (v, submission)).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn the_creator_is_not_counted_as_participant_or_voter() {
+        let creator = Pubkey::new_unique();
+        let others: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+        let mut participants = others.clone();
+        participants.push(creator);
+        let mut voters = others;
+        voters.push(creator);
+
+        let challenge = challenge_with(creator, participants, voters);
+
+        assert_eq!(challenge.counted_participants(), 2);
+        assert_eq!(challenge.counted_voters(), 2);
+        assert!(!challenge.counts_toward_milestones());
+    }
+
+    #[test]
+    fn a_voter_backing_several_submissions_counts_once() {
+        let creator = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        let mut challenge = challenge_with(creator, vec![], vec![]);
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        challenge.add_submission(first, Pubkey::new_unique()).unwrap();
+        challenge.add_submission(second, Pubkey::new_unique()).unwrap();
+        challenge.add_vote(voter, first).unwrap();
+        challenge.add_vote(voter, second).unwrap();
+
+        assert_eq!(challenge.counted_voters(), 1);
+    }
+
+    #[test]
+    fn submissions_remember_their_submitter() {
+        let mut challenge = challenge_with(Pubkey::new_unique(), vec![], vec![]);
+        let submission = Pubkey::new_unique();
+        let submitter = Pubkey::new_unique();
+        challenge.add_submission(submission, submitter).unwrap();
+
+        assert_eq!(challenge.submitter_of(&submission), Some(submitter));
+        assert_eq!(challenge.submitter_of(&Pubkey::new_unique()), None);
+        assert!(challenge.add_submission(submission, Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn votes_for_unknown_submissions_are_rejected() {
+        let mut challenge = challenge_with(Pubkey::new_unique(), vec![], vec![]);
+
+        assert!(challenge.add_vote(Pubkey::new_unique(), Pubkey::new_unique()).is_err());
+        assert!(challenge.voters.is_empty());
+        assert!(challenge.submission_votes.is_empty());
+    }
+
+    #[test]
+    fn a_challenge_with_enough_outside_activity_counts() {
+        let creator = Pubkey::new_unique();
+        let participants: Vec<Pubkey> = (0..MIN_COUNTED_PARTICIPANTS).map(|_| Pubkey::new_unique()).collect();
+        let voters: Vec<Pubkey> = (0..MIN_COUNTED_VOTERS).map(|_| Pubkey::new_unique()).collect();
+
+        assert!(challenge_with(creator, participants, voters).counts_toward_milestones());
+    }
+}
@@ -1,17 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program;
+use anchor_spl::token_interface::Mint;
 use crate::instructions::challenge::types::Challenge;
 use crate::instructions::challenge::errors::ErrorCode;
-
-// Constants for clarity
-pub const TOKEN_2022_PROGRAM_ID_STR: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
-pub const CPT_TOKEN_MINT: &str = "mntjJeXswzxFCnCY1Zs2ekEzDvBVaVdyTVFXbBHfmo9";
+use crate::instructions::challenge::token_transfer::transfer_cpt;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
+use crate::instructions::user_stats::{Activity, ActivityEpoch, EpochActivity, UserStats};
 
 #[derive(Accounts)]
 pub struct VoteForSubmission<'info> {
     #[account(mut)]
     pub voter: Signer<'info>,
     
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
     #[account(
         mut,
         // other constraints remain, but remove the is_active constraint
@@ -23,10 +28,14 @@ pub struct VoteForSubmission<'info> {
     pub treasury: AccountInfo<'info>,
     
     // Token accounts
-    /// CHECK: Token-2022 program
-    #[account(address = TOKEN_2022_PROGRAM_ID_STR.parse::<Pubkey>().unwrap())]
+    /// CHECK: Token program from the program config
+    #[account(address = program_config.token_program @ ErrorCode::InvalidTokenProgram)]
     pub token_program: AccountInfo<'info>,
     
+    // CPT mint from the program config, TransferChecked needs its decimals
+    #[account(address = program_config.cpt_mint @ ErrorCode::InvalidTokenMint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    
     /// CHECK: Voter's token account
     #[account(mut)]
     pub voter_token_account: AccountInfo<'info>,
@@ -45,9 +54,39 @@ pub struct VoteForSubmission<'info> {
     /// CHECK: Voting Treasury's token account
     #[account(mut)]
     pub voting_treasury_token_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"activity_epoch"],
+        bump,
+    )]
+    pub activity_epoch: Box<Account<'info, ActivityEpoch>>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = UserStats::SPACE,
+        seeds = [b"user_stats", voter.key().as_ref()],
+        bump
+    )]
+    pub voter_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = EpochActivity::SPACE,
+        seeds = [b"epoch_activity", activity_epoch.epoch.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub voter_epoch_activity: Box<Account<'info, EpochActivity>>,
+
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handle(ctx: Context<VoteForSubmission>) -> Result<()> {
+// The transfer hook's accounts are passed as remaining accounts
+pub fn handle<'info>(ctx: Context<'_, '_, 'info, 'info, VoteForSubmission<'info>>) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Voting)?;
+    
     let challenge = &mut ctx.accounts.challenge;
     let voter = ctx.accounts.voter.key();
     let submission_id = ctx.accounts.submission_id.key();
@@ -75,28 +114,15 @@ pub fn handle(ctx: Context<VoteForSubmission>) -> Result<()> {
         return Err(ErrorCode::AlreadyVoted.into());
     }
     
-    // Create a Token-2022 Transfer instruction - CORRECT IMPLEMENTATION
-    let transfer_ix = solana_program::instruction::Instruction {
-        program_id: ctx.accounts.token_program.key(),
-        accounts: vec![
-            solana_program::instruction::AccountMeta::new(ctx.accounts.voter_token_account.key(), false),
-            solana_program::instruction::AccountMeta::new(ctx.accounts.voting_treasury_token_account.key(), false),
-            solana_program::instruction::AccountMeta::new_readonly(ctx.accounts.voter.key(), true),
-        ],
-        // Token instruction 3 = Transfer, followed by amount as little-endian bytes
-        data: [3].into_iter()
-              .chain(challenge.voting_fee.to_le_bytes().into_iter())
-              .collect(),
-    };
-    
-    // Execute the transfer with the correct accounts
-    solana_program::program::invoke(
-        &transfer_ix,
-        &[
-            ctx.accounts.voter_token_account.to_account_info(),
-            ctx.accounts.voting_treasury_token_account.to_account_info(),
-            ctx.accounts.voter.to_account_info(),
-        ],
+    transfer_cpt(
+        &ctx.accounts.token_program,
+        &ctx.accounts.voter_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.voting_treasury_token_account,
+        &ctx.accounts.voter.to_account_info(),
+        ctx.remaining_accounts,
+        challenge.voting_fee,
+        &[],
     )?;
     
     // Update voting treasury and total votes
@@ -106,6 +132,9 @@ pub fn handle(ctx: Context<VoteForSubmission>) -> Result<()> {
     // This correctly adds/updates the submission vote
     challenge.add_vote(voter, submission_id)?;
     
+    ctx.accounts.voter_stats.record(voter, Activity::Vote, &mut ctx.accounts.activity_epoch)?;
+    ctx.accounts.voter_epoch_activity.record(voter, ctx.accounts.activity_epoch.epoch, Activity::Vote)?;
+    
     msg!("Vote recorded for submission {}", submission_id);
     
     Ok(())
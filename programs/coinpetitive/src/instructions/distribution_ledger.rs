@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::beneficiary_registry::AllocationCategory;
+use crate::instructions::token_errors::TokenError;
+
+// Cumulative amount paid out per allocation category, through vesting claims and
+// allocation transfers alike. Nothing can push a category past its allocation.
+#[account]
+#[derive(Default, Debug)]
+pub struct DistributionLedger {
+    pub founder_distributed: u64,
+    pub dev_distributed: u64,
+    pub marketing_distributed: u64,
+}
+
+impl DistributionLedger {
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // founder_distributed
+        8 + // dev_distributed
+        8; // marketing_distributed
+
+    pub fn distributed(&self, category: AllocationCategory) -> u64 {
+        match category {
+            AllocationCategory::Founder => self.founder_distributed,
+            AllocationCategory::Dev => self.dev_distributed,
+            AllocationCategory::Marketing => self.marketing_distributed,
+        }
+    }
+
+    pub fn remaining(&self, category: AllocationCategory) -> u64 {
+        category.allocation().saturating_sub(self.distributed(category))
+    }
+
+    // Records a payout and returns what is left of the category's allocation
+    pub fn record(&mut self, category: AllocationCategory, amount: u64) -> Result<u64> {
+        let distributed = match category {
+            AllocationCategory::Founder => &mut self.founder_distributed,
+            AllocationCategory::Dev => &mut self.dev_distributed,
+            AllocationCategory::Marketing => &mut self.marketing_distributed,
+        };
+        let total = distributed
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        require!(total <= category.allocation(), TokenError::ExceedsAllocation);
+        *distributed = total;
+
+        Ok(self.remaining(category))
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeDistributionLedger<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = DistributionLedger::SPACE,
+        seeds = [b"distribution_ledger"],
+        bump
+    )]
+    pub distribution_ledger: Account<'info, DistributionLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ViewDistributionLedger<'info> {
+    #[account(
+        seeds = [b"distribution_ledger"],
+        bump,
+    )]
+    pub distribution_ledger: Account<'info, DistributionLedger>,
+}
+
+pub fn initialize_distribution_ledger(ctx: Context<InitializeDistributionLedger>) -> Result<()> {
+    let ledger = &mut ctx.accounts.distribution_ledger;
+    ledger.founder_distributed = 0;
+    ledger.dev_distributed = 0;
+    ledger.marketing_distributed = 0;
+
+    msg!("Distribution ledger initialized");
+    Ok(())
+}
+
+// Read-only: returns the remaining allocation of a category
+pub fn remaining_allocation(
+    ctx: Context<ViewDistributionLedger>,
+    category: AllocationCategory,
+) -> Result<u64> {
+    let ledger = &ctx.accounts.distribution_ledger;
+    let remaining = ledger.remaining(category);
+
+    msg!("{:?}: {} distributed, {} remaining", category, ledger.distributed(category), remaining);
+    Ok(remaining)
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::instructions::attestor_set::AttestationCluster;
+use crate::instructions::token_errors::TokenError;
+
+// Layout of the ed25519 native program instruction data
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+// Instruction index value meaning "data lives in this same instruction"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+fn read_u16(data: &[u8], at: usize) -> Result<u16> {
+    let bytes = data
+        .get(at..at + 2)
+        .ok_or(TokenError::InvalidAttestation)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+// Every attested message starts with its domain, this program's id and the cluster tag,
+// so a signature only ever verifies for one report type on one deployment
+pub fn attestation_message(domain: &[u8], cluster: AttestationCluster, payload: &[&[u8]]) -> Vec<u8> {
+    let tag = cluster.tag();
+    let payload_len: usize = payload.iter().map(|part| part.len()).sum();
+    let mut message = Vec::with_capacity(domain.len() + 32 + tag.len() + payload_len);
+    message.extend_from_slice(domain);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(tag);
+    for part in payload {
+        message.extend_from_slice(part);
+    }
+    message
+}
+
+// Collect the distinct keys from `allowed` that signed `message` through ed25519
+// program instructions placed earlier in the same transaction
+pub fn verified_signers(
+    instructions_sysvar: &AccountInfo,
+    message: &[u8],
+    allowed: &[Pubkey],
+) -> Result<Vec<Pubkey>> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let mut signers: Vec<Pubkey> = Vec::new();
+
+    for index in 0..current_index {
+        let ix = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+
+        let data = &ix.data;
+        let count = *data.first().ok_or(TokenError::InvalidAttestation)? as usize;
+
+        for i in 0..count {
+            let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SIZE;
+            let signature_ix = read_u16(data, start + 2)?;
+            let pubkey_offset = read_u16(data, start + 4)? as usize;
+            let pubkey_ix = read_u16(data, start + 6)?;
+            let message_offset = read_u16(data, start + 8)? as usize;
+            let message_size = read_u16(data, start + 10)? as usize;
+            let message_ix = read_u16(data, start + 12)?;
+
+            // Only accept signatures whose data is fully contained in the ed25519 instruction
+            require!(
+                signature_ix == CURRENT_INSTRUCTION
+                    && pubkey_ix == CURRENT_INSTRUCTION
+                    && message_ix == CURRENT_INSTRUCTION,
+                TokenError::InvalidAttestation
+            );
+
+            let pubkey_bytes = data
+                .get(pubkey_offset..pubkey_offset + PUBKEY_SIZE)
+                .ok_or(TokenError::InvalidAttestation)?;
+            let signed_message = data
+                .get(message_offset..message_offset + message_size)
+                .ok_or(TokenError::InvalidAttestation)?;
+
+            if signed_message != message {
+                continue;
+            }
+
+            let signer = Pubkey::try_from(pubkey_bytes)
+                .map_err(|_| TokenError::InvalidAttestation)?;
+            if allowed.contains(&signer) && !signers.contains(&signer) {
+                signers.push(signer);
+            }
+        }
+    }
+
+    Ok(signers)
+}
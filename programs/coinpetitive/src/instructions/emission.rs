@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
+use crate::instructions::token_errors::TokenError;
+
+/*
+    milestone mints land in the emission reserve, never in a wallet
+    the reserve can only release into program-owned vaults:
+    airdrop vault       -> milestone airdrops
+    rewards pool vault  -> platform rewards
+    liquidity vault     -> liquidity provisioning
+    the rewards pool and liquidity vaults pay out through vault_release
+*/
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmissionPath {
+    Airdrop,
+    RewardsPool,
+    Liquidity,
+}
+
+impl EmissionPath {
+    pub fn vault_seed(&self) -> &'static [u8] {
+        match self {
+            EmissionPath::Airdrop => b"airdrop_vault",
+            EmissionPath::RewardsPool => b"rewards_pool_vault",
+            EmissionPath::Liquidity => b"liquidity_vault",
+        }
+    }
+}
+
+#[account]
+pub struct EmissionLedger {
+    pub total_emitted: u64,
+    pub released_to_airdrops: u64,
+    pub released_to_rewards_pool: u64,
+    pub released_to_liquidity: u64,
+    pub returned_from_airdrops: u64, // Unclaimed airdrop tokens sent back to the reserve
+}
+
+impl EmissionLedger {
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // total_emitted
+        8 + // released_to_airdrops
+        8 + // released_to_rewards_pool
+        8 + // released_to_liquidity
+        8; // returned_from_airdrops
+
+    pub fn record_release(&mut self, path: EmissionPath, amount: u64) -> Result<()> {
+        let released = match path {
+            EmissionPath::Airdrop => &mut self.released_to_airdrops,
+            EmissionPath::RewardsPool => &mut self.released_to_rewards_pool,
+            EmissionPath::Liquidity => &mut self.released_to_liquidity,
+        };
+        *released = released
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_return(&mut self, amount: u64) -> Result<()> {
+        self.returned_from_airdrops = self.returned_from_airdrops
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeEmissionReserve<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA that owns every program vault, never holds data
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = EmissionLedger::SPACE,
+        seeds = [b"emission_ledger"],
+        bump
+    )]
+    pub emission_ledger: Box<Account<'info, EmissionLedger>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"emission_reserve"],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub emission_reserve: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"airdrop_vault"],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"rewards_pool_vault"],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub rewards_pool_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"liquidity_vault"],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub liquidity_vault: Box<Account<'info, TokenAccount>>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(path: EmissionPath)]
+pub struct ReleaseEmission<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"emission_ledger"],
+        bump,
+    )]
+    pub emission_ledger: Account<'info, EmissionLedger>,
+
+    /// CHECK: PDA that owns every program vault, never holds data
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"emission_reserve"],
+        bump,
+    )]
+    pub emission_reserve: Account<'info, TokenAccount>,
+
+    // Must be the program vault for the chosen path
+    #[account(
+        mut,
+        seeds = [path.vault_seed()],
+        bump,
+    )]
+    pub destination_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn initialize_emission_reserve(ctx: Context<InitializeEmissionReserve>) -> Result<()> {
+    let ledger = &mut ctx.accounts.emission_ledger;
+    ledger.total_emitted = 0;
+    ledger.released_to_airdrops = 0;
+    ledger.released_to_rewards_pool = 0;
+    ledger.released_to_liquidity = 0;
+    ledger.returned_from_airdrops = 0;
+
+    msg!("Emission reserve initialized");
+    Ok(())
+}
+
+pub fn release_emission(ctx: Context<ReleaseEmission>, path: EmissionPath, amount: u64) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Distribution)?;
+
+    require!(
+        ctx.accounts.emission_reserve.amount >= amount,
+        TokenError::InsufficientTokenBalance
+    );
+
+    let seeds = &["vault_authority".as_bytes(), &[ctx.bumps.vault_authority]];
+    let signer = [&seeds[..]];
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.emission_reserve.to_account_info(),
+                to: ctx.accounts.destination_vault.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            &signer,
+        ),
+        amount,
+    )?;
+
+    ctx.accounts.emission_ledger.record_release(path, amount)?;
+
+    msg!("Released {} tokens from the emission reserve to {:?}", amount, path);
+    Ok(())
+}
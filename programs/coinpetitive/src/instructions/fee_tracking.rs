@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::instructions::token_errors::TokenError;
+
 #[account]
 pub struct FeeTracker {
     pub total_participation_fees: u64,
@@ -45,6 +47,20 @@ pub struct UpdateFee<'info> {
     pub fee_tracker: Account<'info, FeeTracker>,
 }
 
+// Hands the tracker to a new authority, e.g. the multisig signer PDA
+#[derive(Accounts)]
+pub struct SetFeeTrackerAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_tracker"],
+        bump,
+        has_one = authority @ TokenError::Unauthorized,
+    )]
+    pub fee_tracker: Account<'info, FeeTracker>,
+}
+
 pub fn initialize_fee_tracker(ctx: Context<InitializeFeeTracker>) -> Result<()> {
     let fee_tracker = &mut ctx.accounts.fee_tracker;
     fee_tracker.total_participation_fees = 0;
@@ -57,6 +73,14 @@ pub fn initialize_fee_tracker(ctx: Context<InitializeFeeTracker>) -> Result<()>
     Ok(())
 }
 
+pub fn set_fee_tracker_authority(ctx: Context<SetFeeTrackerAuthority>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.fee_tracker.authority = new_authority;
+    
+    msg!("Fee tracker authority set to {}", new_authority);
+    
+    Ok(())
+}
+
 pub fn track_participation_fee(ctx: Context<UpdateFee>, amount: u64) -> Result<()> {
     let fee_tracker = &mut ctx.accounts.fee_tracker;
     
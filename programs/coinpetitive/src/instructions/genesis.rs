@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+
+use crate::instructions::program_config::ProgramConfig;
+use crate::instructions::token::TokenState;
+use crate::instructions::token_constants::{
+    CPT_DECIMALS,
+    INITIAL_SUPPLY,
+    FOUNDER_ALLOCATION,
+    DEV_ALLOCATION,
+    AFFILIATOR_ALLOCATION,
+    PUBLIC_ALLOCATION,
+};
+use crate::instructions::token_errors::TokenError;
+
+/*
+    genesis mints the 21M launch supply once, straight into program-owned vaults,
+    signed by the config admin so nobody can front-run it
+    founder vault     1.1M
+    dev vault         500k
+    affiliator vault  500k
+    public vault      18.9M (public use and liquidity pool)
+*/
+
+#[derive(Accounts)]
+pub struct GenesisDistribution<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump,
+        constraint = !token_state.genesis_completed @ TokenError::GenesisAlreadyCompleted,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump,
+        mint::authority = mint,
+        mint::decimals = CPT_DECIMALS,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA that owns every allocation vault, never holds data
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"founder_vault"],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub founder_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"dev_vault"],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub dev_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"affiliator_vault"],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub affiliator_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"public_vault"],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub public_vault: Box<Account<'info, TokenAccount>>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn genesis_distribution(ctx: Context<GenesisDistribution>) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    let seeds = &["mint".as_bytes(), &[ctx.bumps.mint]];
+    let signer = [&seeds[..]];
+
+    let allocations = [
+        (ctx.accounts.founder_vault.to_account_info(), FOUNDER_ALLOCATION),
+        (ctx.accounts.dev_vault.to_account_info(), DEV_ALLOCATION),
+        (ctx.accounts.affiliator_vault.to_account_info(), AFFILIATOR_ALLOCATION),
+        (ctx.accounts.public_vault.to_account_info(), PUBLIC_ALLOCATION),
+    ];
+
+    for (vault, amount) in allocations {
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    authority: ctx.accounts.mint.to_account_info(),
+                    to: vault,
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+                &signer,
+            ),
+            amount,
+        )?;
+    }
+
+    // Record the launch mint; it also starts the 12-month clock for milestone mints
+    let token_state = &mut ctx.accounts.token_state;
+    token_state.record_mint(INITIAL_SUPPLY)?;
+    token_state.last_mint_timestamp = current_timestamp;
+    token_state.genesis_completed = true;
+    token_state.genesis_timestamp = current_timestamp;
+
+    msg!("Genesis distribution minted {} tokens", INITIAL_SUPPLY);
+    msg!("Current supply: {}", token_state.current_supply);
+    Ok(())
+}
@@ -0,0 +1,398 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::{self, Mint};
+use anchor_spl::token_2022;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::instructions::attestor_set::{AttestationCluster, AttestorSet};
+use crate::instructions::ed25519::{attestation_message, verified_signers};
+use crate::instructions::token::TokenState;
+use crate::instructions::token_constants::{MIN_HOLDER_AGE, MIN_HOLDER_BALANCE, MAX_HOLDER_BATCH};
+use crate::instructions::token_errors::TokenError;
+
+/*
+    one HolderRecord per wallet, opened by the wallet owner once its CPT
+    associated token account (legacy or Token-2022) holds MIN_HOLDER_BALANCE
+    a record only counts toward TokenState.unique_wallets (and so the
+    250k / 500k / 1M holder milestones) once confirmed, which needs
+    - an attestation that the wallet never dropped below the minimum between
+      registration and checked_at, at least MIN_HOLDER_AGE later; attestors
+      replay the wallet's transfer history, so one balance passed around
+      between wallets can't confirm more than one of them
+    - the minimum to still be held at confirmation
+    anyone can close a record whose wallet has dropped below the minimum;
+    closing a confirmed record takes it back out of unique_wallets
+*/
+
+// Domain of every holder confirmation attestors sign, see ed25519::attestation_message
+pub const HOLDER_CONFIRMATION_DOMAIN: &[u8] = b"cpt-holder-confirmation";
+
+#[account]
+#[derive(Default, Debug)]
+pub struct HolderRecord {
+    pub wallet: Pubkey,
+    pub registered_at: i64,
+    pub balance_at_registration: u64,
+    pub confirmed_at: i64, // 0 until the record counts as a unique holder
+}
+
+impl HolderRecord {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // wallet
+        8 + // registered_at
+        8 + // balance_at_registration
+        8; // confirmed_at
+
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed_at != 0
+    }
+
+    // checked_at is the end of the attested holding period
+    pub fn can_confirm(&self, checked_at: i64) -> bool {
+        !self.is_confirmed() && checked_at.saturating_sub(self.registered_at) >= MIN_HOLDER_AGE
+    }
+}
+
+// The wallet's associated token account for the legacy or the Token-2022 CPT mint
+pub fn is_holder_token_account(
+    token_account: &Pubkey,
+    token_program: &Pubkey,
+    account_mint: &Pubkey,
+    wallet: &Pubkey,
+    legacy_mint: &Pubkey,
+    mint_2022: &Pubkey,
+) -> bool {
+    let known_mint = (account_mint == legacy_mint && *token_program == token::ID)
+        || (account_mint == mint_2022 && *mint_2022 != Pubkey::default() && *token_program == token_2022::ID);
+    known_mint
+        && *token_account == get_associated_token_address_with_program_id(wallet, account_mint, token_program)
+}
+
+// Bytes each attestor signs for a confirmation batch: every (wallet, registered_at)
+// in the batch, in order, and the end of the holding period they vouch for.
+// A re-registered wallet has a new registered_at, so old signatures never carry over.
+pub fn holder_confirmation_message(
+    cluster: AttestationCluster,
+    records: &[(Pubkey, i64)],
+    checked_at: i64,
+) -> Vec<u8> {
+    let entries: Vec<Vec<u8>> = records
+        .iter()
+        .map(|(wallet, registered_at)| [wallet.as_ref(), &registered_at.to_le_bytes()].concat())
+        .collect();
+    let parts: Vec<&[u8]> = entries.iter().map(|entry| entry.as_slice()).collect();
+    let batch_hash = hashv(&parts).to_bytes();
+    attestation_message(
+        HOLDER_CONFIRMATION_DOMAIN,
+        cluster,
+        &[&batch_hash, &checked_at.to_le_bytes()],
+    )
+}
+
+#[derive(Accounts)]
+pub struct RegisterHolder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    // The owner's legacy or Token-2022 CPT associated token account
+    #[account(
+        constraint = is_holder_token_account(
+            &holder_token_account.key(),
+            holder_token_account.to_account_info().owner,
+            &holder_token_account.mint,
+            &owner.key(),
+            &mint.key(),
+            &token_state.token_2022_mint,
+        ) @ TokenError::OwnerMismatch,
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = HolderRecord::SPACE,
+        seeds = [b"holder_record", owner.key().as_ref()],
+        bump
+    )]
+    pub holder_record: Account<'info, HolderRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Batch crank: remaining accounts are (holder associated token account, holder record PDA) pairs,
+// legacy or Token-2022 accounts alike
+#[derive(Accounts)]
+pub struct ConfirmHolders<'info> {
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    #[account(
+        seeds = [b"attestor_set"],
+        bump,
+    )]
+    pub attestor_set: Account<'info, AttestorSet>,
+
+    /// CHECK: Instructions sysvar, used to read the ed25519 signature instructions
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseHolderRecord<'info> {
+    /// CHECK: only receives the record's rent back
+    #[account(
+        mut,
+        address = holder_record.wallet @ TokenError::OwnerMismatch,
+    )]
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: the wallet's legacy CPT associated token account, checked in the handler and read as a zero balance once closed
+    pub holder_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: the wallet's Token-2022 CPT associated token account, checked in the handler and read as a zero balance once closed
+    pub holder_token_2022_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = wallet,
+        seeds = [b"holder_record", wallet.key().as_ref()],
+        bump
+    )]
+    pub holder_record: Account<'info, HolderRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Account<'info, TokenState>,
+}
+
+pub fn register_holder(ctx: Context<RegisterHolder>) -> Result<()> {
+    let wallet = ctx.accounts.owner.key();
+    let holder_record = &mut ctx.accounts.holder_record;
+
+    if holder_record.wallet != Pubkey::default() {
+        msg!("Wallet {} is already tracked", wallet);
+        return Ok(());
+    }
+
+    let balance = ctx.accounts.holder_token_account.amount;
+    require!(balance >= MIN_HOLDER_BALANCE, TokenError::InsufficientTokenBalance);
+
+    holder_record.wallet = wallet;
+    holder_record.registered_at = Clock::get()?.unix_timestamp;
+    holder_record.balance_at_registration = balance;
+    holder_record.confirmed_at = 0;
+
+    msg!("Registered holder {}, countable after {} seconds", wallet, MIN_HOLDER_AGE);
+    Ok(())
+}
+
+// usize::is_multiple_of is newer than the rustc shipped with the Solana platform tools
+#[allow(clippy::manual_is_multiple_of)]
+pub fn confirm_holders<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConfirmHolders<'info>>,
+    checked_at: i64,
+) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len() % 2 == 0 && remaining.len() / 2 <= MAX_HOLDER_BATCH,
+        TokenError::InvalidBatchSize
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(checked_at <= now, TokenError::InvalidAttestation);
+
+    let legacy_mint = ctx.accounts.mint.key();
+    let mint_2022 = ctx.accounts.token_state.token_2022_mint;
+    let mut batch = Vec::with_capacity(remaining.len() / 2);
+
+    for pair in remaining.chunks(2) {
+        let token_account = InterfaceAccount::<TokenAccount>::try_from(&pair[0])?;
+        let record = Account::<HolderRecord>::try_from(&pair[1])?;
+
+        let wallet = record.wallet;
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"holder_record", wallet.as_ref()],
+            ctx.program_id,
+        );
+        require!(record.key() == expected, TokenError::OwnerMismatch);
+        require!(
+            is_holder_token_account(
+                &token_account.key(),
+                pair[0].owner,
+                &token_account.mint,
+                &wallet,
+                &legacy_mint,
+                &mint_2022,
+            ),
+            TokenError::OwnerMismatch
+        );
+
+        batch.push((token_account, record));
+    }
+
+    // Enough configured attestors must vouch for the holding period of the whole batch
+    let attestor_set = &ctx.accounts.attestor_set;
+    let records: Vec<(Pubkey, i64)> = batch
+        .iter()
+        .map(|(_, record)| (record.wallet, record.registered_at))
+        .collect();
+    let message = holder_confirmation_message(attestor_set.cluster, &records, checked_at);
+    let signers = verified_signers(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &message,
+        &attestor_set.attestors,
+    )?;
+    require!(
+        signers.len() >= attestor_set.threshold as usize,
+        TokenError::InsufficientAttestations
+    );
+
+    let mut confirmed: u64 = 0;
+    for (token_account, mut record) in batch {
+        let wallet = record.wallet;
+
+        // Skipped rather than rejected so one stale pair doesn't fail the batch
+        if !record.can_confirm(checked_at) {
+            msg!("Wallet {} is not ready to confirm", wallet);
+            continue;
+        }
+        if token_account.amount < MIN_HOLDER_BALANCE {
+            msg!("Wallet {} is below the holder minimum", wallet);
+            continue;
+        }
+
+        record.confirmed_at = now;
+        record.exit(ctx.program_id)?;
+
+        ctx.accounts.token_state.record_new_holder()?;
+        confirmed += 1;
+    }
+
+    msg!("Confirmed {} holders", confirmed);
+    msg!("Unique wallets: {}", ctx.accounts.token_state.unique_wallets);
+    Ok(())
+}
+
+// A closed or never created account reads as a zero balance
+fn holder_balance(token_account: &AccountInfo) -> Result<u64> {
+    if token_account.data_is_empty() {
+        return Ok(0);
+    }
+    require!(
+        token_account.owner == &token::ID || token_account.owner == &token_2022::ID,
+        TokenError::OwnerMismatch
+    );
+    Ok(TokenAccount::try_deserialize(&mut &token_account.try_borrow_data()?[..])?.amount)
+}
+
+pub fn close_holder_record(ctx: Context<CloseHolderRecord>) -> Result<()> {
+    let wallet = ctx.accounts.wallet.key();
+    require_keys_eq!(
+        ctx.accounts.holder_token_account.key(),
+        get_associated_token_address_with_program_id(&wallet, &ctx.accounts.mint.key(), &token::ID),
+        TokenError::OwnerMismatch
+    );
+    require_keys_eq!(
+        ctx.accounts.holder_token_2022_account.key(),
+        get_associated_token_address_with_program_id(&wallet, &ctx.accounts.token_state.token_2022_mint, &token_2022::ID),
+        TokenError::OwnerMismatch
+    );
+
+    // Neither the legacy nor the Token-2022 account may still hold the minimum
+    let legacy_balance = holder_balance(&ctx.accounts.holder_token_account)?;
+    let balance_2022 = holder_balance(&ctx.accounts.holder_token_2022_account)?;
+    require!(
+        legacy_balance < MIN_HOLDER_BALANCE && balance_2022 < MIN_HOLDER_BALANCE,
+        TokenError::HolderStillEligible
+    );
+
+    if ctx.accounts.holder_record.is_confirmed() {
+        ctx.accounts.token_state.record_holder_removed()?;
+    }
+
+    msg!("Closed holder record for {}", ctx.accounts.wallet.key());
+    msg!("Unique wallets: {}", ctx.accounts.token_state.unique_wallets);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_record_confirms_only_after_the_minimum_age() {
+        let record = HolderRecord { registered_at: 1_000, ..Default::default() };
+
+        assert!(!record.can_confirm(1_000 + MIN_HOLDER_AGE - 1));
+        assert!(record.can_confirm(1_000 + MIN_HOLDER_AGE));
+    }
+
+    #[test]
+    fn a_confirmed_record_is_not_confirmed_again() {
+        let record = HolderRecord { registered_at: 1_000, confirmed_at: 5_000_000, ..Default::default() };
+
+        assert!(record.is_confirmed());
+        assert!(!record.can_confirm(1_000 + 2 * MIN_HOLDER_AGE));
+    }
+
+    #[test]
+    fn legacy_and_token_2022_associated_accounts_are_accepted() {
+        let wallet = Pubkey::new_unique();
+        let legacy_mint = Pubkey::new_unique();
+        let mint_2022 = Pubkey::new_unique();
+        let legacy_ata = get_associated_token_address_with_program_id(&wallet, &legacy_mint, &token::ID);
+        let ata_2022 = get_associated_token_address_with_program_id(&wallet, &mint_2022, &token_2022::ID);
+
+        assert!(is_holder_token_account(&legacy_ata, &token::ID, &legacy_mint, &wallet, &legacy_mint, &mint_2022));
+        assert!(is_holder_token_account(&ata_2022, &token_2022::ID, &mint_2022, &wallet, &legacy_mint, &mint_2022));
+        // Another wallet's account, or one of another mint, is not
+        let other_wallet = Pubkey::new_unique();
+        assert!(!is_holder_token_account(&legacy_ata, &token::ID, &legacy_mint, &other_wallet, &legacy_mint, &mint_2022));
+        let other_mint = Pubkey::new_unique();
+        let other_ata = get_associated_token_address_with_program_id(&wallet, &other_mint, &token::ID);
+        assert!(!is_holder_token_account(&other_ata, &token::ID, &other_mint, &wallet, &legacy_mint, &mint_2022));
+    }
+
+    #[test]
+    fn a_confirmation_attestation_is_bound_to_each_registration() {
+        let wallet = Pubkey::new_unique();
+        let cluster = AttestationCluster::Devnet;
+        let message = holder_confirmation_message(cluster, &[(wallet, 1_000)], 5_000_000);
+
+        assert_ne!(message, holder_confirmation_message(cluster, &[(wallet, 1_001)], 5_000_000));
+        assert_ne!(message, holder_confirmation_message(cluster, &[(wallet, 1_000)], 5_000_001));
+    }
+}
@@ -1,102 +1,92 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use std::str::FromStr;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::instructions::beneficiary_registry::{AllocationCategory, BeneficiaryRegistry};
+use crate::instructions::distribution_ledger::DistributionLedger;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
+use crate::instructions::token_errors::TokenError;
 
 /*
     token allocation at launch is 21M
-    founder gets 1.1M
-    developer 500k
-    Affiliator Rewards 500k
+    founder gets 1.1M (vested, see vesting.rs)
+    developer 500k (vested, see vesting.rs)
+    Affiliator Rewards 500k (marketing, paid by the admin through allocation_transfer)
     18.9 for public use and liquidity pool
 */
 
-
-
 #[derive(Accounts)]
+#[instruction(category: AllocationCategory)]
 pub struct PartiesTr<'info> {
-    #[account(mut)]
-    pub from: Account<'info, TokenAccount>,
-    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+    /// CHECK: PDA that owns every program vault, never holds data
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    // The category's genesis vault
+    #[account(
+        mut,
+        seeds = [category.vault_seed()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    // Must belong to the category's wallet in the beneficiary registry
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = to.owner == beneficiary_registry.wallet_for(category) @ TokenError::InvalidBeneficiary,
+    )]
     pub to: Account<'info, TokenAccount>,
-    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"beneficiary_registry"],
+        bump,
+    )]
+    pub beneficiary_registry: Account<'info, BeneficiaryRegistry>,
+    #[account(
+        mut,
+        seeds = [b"distribution_ledger"],
+        bump,
+    )]
+    pub distribution_ledger: Account<'info, DistributionLedger>,
     pub token_program: Program<'info, Token>,
 }
 
-#[error_code]
-pub enum TokenErr {
-    #[msg("Not the owner")]
-    YouNoTokenOwner,
-}
-
-//transfers to founder team wallet
-pub fn founder_transfer(ctx: Context<PartiesTr>, amount: u64) -> Result<()> {
-    let founder_wallet = Pubkey::from_str("FuFzoMF5xTwZego84fRoscnart4dPYNkpHho2UBe7NDt")
-        .expect("Failed to parse founder wallet");
-    require!(
-        ctx.accounts.to.owner == founder_wallet,
-        TokenErr::YouNoTokenOwner
-    );
-    
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.from.to_account_info(),
-        to: ctx.accounts.to.to_account_info(),
-        authority: ctx.accounts.authority.to_account_info(),
-    };
-    
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    
-    token::transfer(cpi_ctx, amount)?;
-    
-    Ok(())
-}
-
-//transfers to dev team wallet
-pub fn dev_transfer(ctx: Context<PartiesTr>, amount: u64) -> Result<()> {
-    let dev_wallet = Pubkey::from_str("8zhGg2MhHb4aGDa62jymyUTT3mkzQAyqPJme4Cyn6iYh")
-        .expect("Failed to parse dev wallet");
+// Transfers out of a non-vested category's vault to its registered wallet.
+// Returns the category's remaining allocation.
+pub fn allocation_transfer(ctx: Context<PartiesTr>, category: AllocationCategory, amount: u64) -> Result<u64> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Distribution)?;
+    require!(!category.is_vested(), TokenError::VestedCategory);
     
-    require!(
-        ctx.accounts.to.owner == dev_wallet,
-        TokenErr::YouNoTokenOwner
-    );
+    let remaining = ctx.accounts.distribution_ledger.record(category, amount)?;
     
+    let seeds = &["vault_authority".as_bytes(), &[ctx.bumps.vault_authority]];
+    let signer = [&seeds[..]];
     let cpi_accounts = Transfer {
-        from: ctx.accounts.from.to_account_info(),
+        from: ctx.accounts.vault.to_account_info(),
         to: ctx.accounts.to.to_account_info(),
-        authority: ctx.accounts.authority.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
     };
     
     let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer);
     
     token::transfer(cpi_ctx, amount)?;
     
-    Ok(())
+    msg!("Transferred {} tokens from the {:?} vault to its wallet", amount, category);
+    msg!("Remaining {:?} allocation: {}", category, remaining);
+    Ok(remaining)
 }
-
-
-//transfers to marketing team wallet
-pub fn do_marketing_transfer(ctx: Context<PartiesTr>, amount: u64) -> Result<()> {
-    let marketing_wallet = Pubkey::from_str("7vyM8kJQwUbcgWHqNiqmHzLFbeALVyZPT3Rry9WgCnTA")
-            .expect("Failed to parse marketing wallet");
-    
-    require!(
-        ctx.accounts.to.owner == marketing_wallet,
-        TokenErr::YouNoTokenOwner
-    );
-    
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.from.to_account_info(),
-        to: ctx.accounts.to.to_account_info(),
-        authority: ctx.accounts.authority.to_account_info(),
-    };
-    
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    
-    token::transfer(cpi_ctx, amount)?;
-    
-    Ok(())
-}
-
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+
+use crate::instructions::attestor_set::{AttestationCluster, AttestorSet, MAX_ATTESTORS};
+use crate::instructions::ed25519::{attestation_message, verified_signers};
+use crate::instructions::token_errors::TokenError;
+
+/*
+    attested reports of off-chain milestone metrics, each kept as an
+    auditable MilestoneReport record
+    the mint triggers themselves are driven on-chain (unique_wallets by
+    HolderRecord confirmations, is_self_sustaining by the revenue ledger),
+    so a report is evidence for review and never changes TokenState
+*/
+
+// Domain of every milestone report attestors sign, see ed25519::attestation_message
+pub const MILESTONE_REPORT_DOMAIN: &[u8] = b"cpt-milestone-report";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MilestoneKind {
+    UniqueWallets,
+    SelfSustaining,
+}
+
+// Auditable record of a single attested milestone report
+#[account]
+pub struct MilestoneReport {
+    pub index: u64,
+    pub kind: MilestoneKind,
+    pub value: u64,
+    pub evidence_hash: [u8; 32],
+    pub attestors: Vec<Pubkey>,
+    pub reporter: Pubkey,
+    pub reported_at: i64,
+}
+
+impl MilestoneReport {
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // index
+        1 + // kind
+        8 + // value
+        32 + // evidence_hash
+        4 + (32 * MAX_ATTESTORS) + // attestors
+        32 + // reporter
+        8; // reported_at
+}
+
+#[derive(Accounts)]
+pub struct ReportMilestone<'info> {
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"attestor_set"],
+        bump,
+    )]
+    pub attestor_set: Account<'info, AttestorSet>,
+
+    #[account(
+        init,
+        payer = reporter,
+        space = MilestoneReport::SPACE,
+        seeds = [b"milestone_report", attestor_set.report_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub milestone_report: Account<'info, MilestoneReport>,
+
+    /// CHECK: Instructions sysvar, used to read the ed25519 signature instructions
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Bytes each attestor signs for a report. The report index makes every
+// signature single use.
+pub fn milestone_report_message(
+    cluster: AttestationCluster,
+    index: u64,
+    kind: MilestoneKind,
+    value: u64,
+    evidence_hash: &[u8; 32],
+) -> Vec<u8> {
+    attestation_message(
+        MILESTONE_REPORT_DOMAIN,
+        cluster,
+        &[&index.to_le_bytes(), &[kind as u8], &value.to_le_bytes(), evidence_hash],
+    )
+}
+
+pub fn report_milestone(
+    ctx: Context<ReportMilestone>,
+    kind: MilestoneKind,
+    value: u64,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    let attestor_set = &mut ctx.accounts.attestor_set;
+    let index = attestor_set.report_count;
+
+    // Enough configured attestors must have signed this exact report
+    let message = milestone_report_message(attestor_set.cluster, index, kind, value, &evidence_hash);
+    let signers = verified_signers(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &message,
+        &attestor_set.attestors,
+    )?;
+    require!(
+        signers.len() >= attestor_set.threshold as usize,
+        TokenError::InsufficientAttestations
+    );
+
+    let report = &mut ctx.accounts.milestone_report;
+    report.index = index;
+    report.kind = kind;
+    report.value = value;
+    report.evidence_hash = evidence_hash;
+    report.attestors = signers;
+    report.reporter = ctx.accounts.reporter.key();
+    report.reported_at = Clock::get()?.unix_timestamp;
+
+    attestor_set.report_count = index
+        .checked_add(1)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+
+    msg!("Milestone report {} recorded: {:?} = {}", index, kind, value);
+    Ok(())
+}
@@ -10,3 +10,42 @@ pub mod token_constants;
 pub use token_constants::*;
 pub mod fee_tracking;
 pub use fee_tracking::*;
+pub mod ed25519;
+pub mod attestor_set;
+pub use attestor_set::*;
+pub mod milestone_report;
+pub use milestone_report::*;
+pub mod genesis;
+pub use genesis::*;
+pub mod emission;
+pub use emission::*;
+pub mod vault_release;
+pub use vault_release::*;
+pub mod sell_limit;
+pub use sell_limit::*;
+pub mod pool_registry;
+pub use pool_registry::*;
+pub mod token_2022_migration;
+pub use token_2022_migration::*;
+pub mod holder_record;
+pub use holder_record::*;
+pub mod revenue_ledger;
+pub use revenue_ledger::*;
+pub mod program_config;
+pub use program_config::*;
+pub mod vesting;
+pub use vesting::*;
+pub mod beneficiary_registry;
+pub use beneficiary_registry::*;
+pub mod distribution_ledger;
+pub use distribution_ledger::*;
+pub mod referral;
+pub use referral::*;
+pub mod airdrop;
+pub use airdrop::*;
+pub mod random_draw;
+pub use random_draw::*;
+pub mod user_stats;
+pub use user_stats::*;
+pub mod multisig;
+pub use multisig::*;
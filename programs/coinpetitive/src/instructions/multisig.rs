@@ -0,0 +1,368 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::instructions::program_config::ProgramConfig;
+use crate::instructions::token_errors::TokenError;
+
+/*
+    m-of-n multisig for admin operations
+    the multisig_signer PDA is meant to become config.admin (which gates allocation
+    transfers, vault releases and attestor set changes), config.guardian, the fee
+    and challenge tracker authority, and the owner of any wallet whose transfers
+    should need several keys
+    an owner proposes an instruction, owners approve it, and once `threshold`
+    approvals are in any owner executes it, signed by multisig_signer
+    proposals are numbered, expire, execute at most once, and are voided when
+    the owner set changes
+*/
+
+pub const MAX_MULTISIG_OWNERS: usize = 10; // Approvals are a u16 bitmask over owner indexes
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 16;
+pub const MAX_PROPOSAL_DATA_LEN: usize = 512;
+pub const MAX_PROPOSAL_LIFETIME: i64 = 1_209_600; // 60 * 60 * 24 * 14, two weeks
+
+#[account]
+pub struct Multisig {
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64, // Index of the next proposal
+    pub owner_set_seqno: u32, // Bumped on every owner change, voiding open proposals
+}
+
+impl Multisig {
+    pub const SPACE: usize = 8 + // discriminator
+        4 + (32 * MAX_MULTISIG_OWNERS) + // owners
+        1 + // threshold
+        8 + // proposal_count
+        4; // owner_set_seqno
+
+    fn owner_index(&self, key: &Pubkey) -> Result<usize> {
+        self.owners
+            .iter()
+            .position(|owner| owner == key)
+            .ok_or(error!(TokenError::NotMultisigOwner))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl ProposalAccount {
+    pub const SPACE: usize = 32 + // pubkey
+        1 + // is_signer
+        1; // is_writable
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProposedInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccount>,
+    pub data: Vec<u8>,
+}
+
+impl ProposedInstruction {
+    pub const SPACE: usize = 32 + // program_id
+        4 + (ProposalAccount::SPACE * MAX_PROPOSAL_ACCOUNTS) + // accounts
+        4 + MAX_PROPOSAL_DATA_LEN; // data
+}
+
+#[account]
+pub struct Proposal {
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub instruction: ProposedInstruction,
+    pub approvals: u16, // Bit i set when owners[i] approved
+    pub owner_set_seqno: u32,
+    pub expires_at: i64,
+    pub executed: bool,
+}
+
+impl Proposal {
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // index
+        32 + // proposer
+        ProposedInstruction::SPACE + // instruction
+        2 + // approvals
+        4 + // owner_set_seqno
+        8 + // expires_at
+        1; // executed
+
+    fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+
+    // Open proposals can still be approved and executed
+    fn require_open(&self, multisig: &Multisig, timestamp: i64) -> Result<()> {
+        require!(!self.executed, TokenError::ProposalAlreadyExecuted);
+        require!(timestamp < self.expires_at, TokenError::ProposalExpired);
+        require!(
+            self.owner_set_seqno == multisig.owner_set_seqno,
+            TokenError::ProposalOwnersChanged
+        );
+        Ok(())
+    }
+}
+
+fn validate_owners(owners: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !owners.is_empty() && owners.len() <= MAX_MULTISIG_OWNERS,
+        TokenError::InvalidMultisig
+    );
+    require!(
+        threshold > 0 && threshold as usize <= owners.len(),
+        TokenError::InvalidMultisig
+    );
+    for (i, owner) in owners.iter().enumerate() {
+        require!(!owners[..i].contains(owner), TokenError::InvalidMultisig);
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Multisig::SPACE,
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only reachable through an executed proposal
+#[derive(Accounts)]
+pub struct SetMultisigOwners<'info> {
+    #[account(
+        seeds = [b"multisig_signer"],
+        bump,
+    )]
+    pub multisig_signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeMultisigTransaction<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::SPACE,
+        seeds = [b"proposal", multisig.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMultisigTransaction<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.index.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+}
+
+// The proposed instruction's accounts are passed as remaining accounts
+#[derive(Accounts)]
+pub struct ExecuteMultisigTransaction<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.index.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// CHECK: PDA that signs executed proposals, never holds data
+    #[account(
+        seeds = [b"multisig_signer"],
+        bump,
+    )]
+    pub multisig_signer: UncheckedAccount<'info>,
+}
+
+pub fn create_multisig(
+    ctx: Context<CreateMultisig>,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    validate_owners(&owners, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.owners = owners;
+    multisig.threshold = threshold;
+    multisig.proposal_count = 0;
+    multisig.owner_set_seqno = 0;
+
+    msg!("Multisig created: {} of {}", threshold, multisig.owners.len());
+    Ok(())
+}
+
+pub fn set_multisig_owners(
+    ctx: Context<SetMultisigOwners>,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    validate_owners(&owners, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.owners = owners;
+    multisig.threshold = threshold;
+    multisig.owner_set_seqno = multisig.owner_set_seqno
+        .checked_add(1)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+
+    msg!("Multisig owners set: {} of {}", threshold, multisig.owners.len());
+    Ok(())
+}
+
+pub fn propose_multisig_transaction(
+    ctx: Context<ProposeMultisigTransaction>,
+    instruction: ProposedInstruction,
+    expires_at: i64,
+) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        instruction.accounts.len() <= MAX_PROPOSAL_ACCOUNTS
+            && instruction.data.len() <= MAX_PROPOSAL_DATA_LEN,
+        TokenError::InvalidProposal
+    );
+    require!(
+        expires_at > current_timestamp && expires_at - current_timestamp <= MAX_PROPOSAL_LIFETIME,
+        TokenError::InvalidProposal
+    );
+
+    let multisig = &mut ctx.accounts.multisig;
+    let proposer = ctx.accounts.proposer.key();
+    let owner_index = multisig.owner_index(&proposer)?;
+    let index = multisig.proposal_count;
+    multisig.proposal_count = index
+        .checked_add(1)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+
+    // Proposing counts as the proposer's approval
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.index = index;
+    proposal.proposer = proposer;
+    proposal.instruction = instruction;
+    proposal.approvals = 1 << owner_index;
+    proposal.owner_set_seqno = multisig.owner_set_seqno;
+    proposal.expires_at = expires_at;
+    proposal.executed = false;
+
+    msg!("Proposal {} created by {}, expires at {}", index, proposer, expires_at);
+    Ok(())
+}
+
+pub fn approve_multisig_transaction(ctx: Context<ApproveMultisigTransaction>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.require_open(multisig, Clock::get()?.unix_timestamp)?;
+
+    let owner_index = multisig.owner_index(&ctx.accounts.owner.key())?;
+    require!(proposal.approvals & (1 << owner_index) == 0, TokenError::AlreadyApproved);
+    proposal.approvals |= 1 << owner_index;
+
+    msg!(
+        "Proposal {} approved by {}: {} of {}",
+        proposal.index,
+        ctx.accounts.owner.key(),
+        proposal.approval_count(),
+        multisig.threshold
+    );
+    Ok(())
+}
+
+pub fn execute_multisig_transaction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteMultisigTransaction<'info>>,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    multisig.owner_index(&ctx.accounts.owner.key())?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.require_open(multisig, Clock::get()?.unix_timestamp)?;
+    require!(
+        proposal.approval_count() >= multisig.threshold as u32,
+        TokenError::InsufficientApprovals
+    );
+
+    // Marked before the call so the proposal can't be executed again from inside it
+    proposal.executed = true;
+
+    let multisig_signer = ctx.accounts.multisig_signer.key();
+    let instruction = Instruction {
+        program_id: proposal.instruction.program_id,
+        accounts: proposal.instruction.accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.pubkey,
+                is_signer: account.is_signer || account.pubkey == multisig_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: proposal.instruction.data.clone(),
+    };
+
+    // The proposal account is written back by Anchor after the call, so persist it first
+    proposal.exit(ctx.program_id)?;
+
+    let seeds = &["multisig_signer".as_bytes(), &[ctx.bumps.multisig_signer]];
+    let signer = [&seeds[..]];
+    let mut account_infos = ctx.remaining_accounts.to_vec();
+    account_infos.push(ctx.accounts.multisig_signer.to_account_info());
+    invoke_signed(&instruction, &account_infos, &signer)?;
+
+    msg!("Proposal {} executed", proposal.index);
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::program_config::ProgramConfig;
+use crate::instructions::token_errors::TokenError;
+
+pub const MAX_POOLS: usize = 32;
+
+// Token accounts of recognized liquidity pools; only trades touching one of
+// these are burned and counted against the sell limit
+#[account]
+pub struct PoolRegistry {
+    pub pools: Vec<Pubkey>,
+}
+
+impl PoolRegistry {
+    pub const SPACE: usize = 8 + // discriminator
+        4 + (32 * MAX_POOLS); // pools
+
+    pub fn is_pool(&self, token_account: &Pubkey) -> bool {
+        self.pools.contains(token_account)
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePoolRegistry<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PoolRegistry::SPACE,
+        seeds = [b"pool_registry"],
+        bump
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePoolRegistry<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_registry"],
+        bump,
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+}
+
+pub fn initialize_pool_registry(ctx: Context<InitializePoolRegistry>) -> Result<()> {
+    ctx.accounts.pool_registry.pools = Vec::new();
+
+    msg!("Pool registry initialized");
+    Ok(())
+}
+
+pub fn add_pool(ctx: Context<UpdatePoolRegistry>, pool: Pubkey) -> Result<()> {
+    let registry = &mut ctx.accounts.pool_registry;
+    require!(!registry.is_pool(&pool), TokenError::PoolAlreadyRegistered);
+    require!(registry.pools.len() < MAX_POOLS, TokenError::PoolRegistryFull);
+
+    registry.pools.push(pool);
+
+    msg!("Registered liquidity pool account {}", pool);
+    Ok(())
+}
+
+pub fn remove_pool(ctx: Context<UpdatePoolRegistry>, pool: Pubkey) -> Result<()> {
+    let registry = &mut ctx.accounts.pool_registry;
+    let index = registry.pools
+        .iter()
+        .position(|p| *p == pool)
+        .ok_or(TokenError::PoolNotRegistered)?;
+
+    registry.pools.remove(index);
+
+    msg!("Removed liquidity pool account {}", pool);
+    Ok(())
+}
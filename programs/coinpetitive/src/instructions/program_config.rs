@@ -0,0 +1,397 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::token::TokenState;
+use crate::instructions::token_errors::TokenError;
+
+/*
+    cluster specific settings, read by every instruction instead of compiled-in constants
+    so devnet and mainnet can differ without recompiling
+    config.admin is the program admin for every admin-gated instruction, and is
+    rotated through the same queued update as every other parameter
+    config.guardian can pause each instruction family on its own; unpausing
+    waits config.unpause_delay when one is set
+    parameter changes, the guardian and the unpause delay included, are queued first
+    and can only be applied CONFIG_UPDATE_DELAY later, so holders see every change
+    coming; the admin can cancel a queued change until it is applied
+*/
+
+pub const MAX_POOL_BURN_RATE: u8 = 10; // Percent
+pub const MAX_SELL_LIMIT_PERCENT: u8 = 100;
+pub const CONFIG_UPDATE_DELAY: i64 = 172_800; // 60 * 60 * 24 * 2, two days
+pub const MAX_UNPAUSE_DELAY: i64 = 2_592_000; // 60 * 60 * 24 * 30, 30 days
+pub const INSTRUCTION_FAMILY_COUNT: usize = 7;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstructionFamily {
+    Minting, // Milestone mints
+    ChallengeCreation,
+    Participation, // Participation fees and video submissions
+    Voting,
+    Finalization, // Finalization, voter rewards and creator claims
+    Distribution, // Allocation transfers, vesting, referral, airdrop and emission payouts
+    Migration, // Legacy to Token-2022 swaps
+}
+
+impl InstructionFamily {
+    fn bit(&self) -> u8 {
+        1 << (*self as u8)
+    }
+}
+
+#[account]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub cpt_mint: Pubkey, // Mint challenges are paid in
+    pub token_program: Pubkey, // Token program that owns cpt_mint
+    pub pool_burn_rate: u8, // Percent burned on liquidity pool trades
+    pub submission_fee: u64, // Tokens paid per video submission
+    pub creation_fee: u64, // Lamports paid per challenge creation
+    pub gas_deposit: u64, // Lamports funded into each challenge treasury PDA
+    pub platform_fee: u64, // Lamports paid per challenge finalization and creator claim
+    pub referral_bonus: u64, // Tokens credited to a referrer per referee, 0 disables bonuses
+    pub referrer_bonus_cap: u64, // Most tokens a single referrer can earn
+    pub sell_limit_percent: u8, // Percent of circulating supply a wallet can sell per 24 hours
+    pub guardian: Pubkey, // Only key that can pause and unpause
+    pub paused: u8, // One bit per InstructionFamily
+    pub unpause_delay: i64, // Seconds between requesting and executing an unpause, 0 for none
+    pub unpause_eligible_at: [i64; INSTRUCTION_FAMILY_COUNT], // Per family, 0 when no unpause is pending
+}
+
+impl ProgramConfig {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // admin
+        32 + // cpt_mint
+        32 + // token_program
+        1 + // pool_burn_rate
+        8 + // submission_fee
+        8 + // creation_fee
+        8 + // gas_deposit
+        8 + // platform_fee
+        8 + // referral_bonus
+        8 + // referrer_bonus_cap
+        1 + // sell_limit_percent
+        32 + // guardian
+        1 + // paused
+        8 + // unpause_delay
+        (8 * INSTRUCTION_FAMILY_COUNT); // unpause_eligible_at
+
+    fn apply(&mut self, params: ProgramConfigParams) -> Result<()> {
+        params.validate()?;
+        self.admin = params.admin;
+        self.cpt_mint = params.cpt_mint;
+        self.token_program = params.token_program;
+        self.pool_burn_rate = params.pool_burn_rate;
+        self.submission_fee = params.submission_fee;
+        self.creation_fee = params.creation_fee;
+        self.gas_deposit = params.gas_deposit;
+        self.platform_fee = params.platform_fee;
+        self.referral_bonus = params.referral_bonus;
+        self.referrer_bonus_cap = params.referrer_bonus_cap;
+        self.sell_limit_percent = params.sell_limit_percent;
+        self.guardian = params.guardian;
+        self.unpause_delay = params.unpause_delay;
+        Ok(())
+    }
+
+    pub fn pool_burn(&self, amount: u64) -> u64 {
+        amount * self.pool_burn_rate as u64 / 100
+    }
+
+    pub fn daily_sell_limit(&self, circulating_supply: u64) -> u64 {
+        circulating_supply * self.sell_limit_percent as u64 / 100
+    }
+
+    pub fn is_paused(&self, family: InstructionFamily) -> bool {
+        self.paused & family.bit() != 0
+    }
+
+    // Called first thing by every handler of a pausable family
+    pub fn require_active(&self, family: InstructionFamily) -> Result<()> {
+        require!(!self.is_paused(family), TokenError::InstructionPaused);
+        Ok(())
+    }
+
+    fn set_unpaused(&mut self, family: InstructionFamily) {
+        self.paused &= !family.bit();
+        self.unpause_eligible_at[family as usize] = 0;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct ProgramConfigParams {
+    pub admin: Pubkey,
+    pub cpt_mint: Pubkey,
+    pub token_program: Pubkey,
+    pub pool_burn_rate: u8,
+    pub submission_fee: u64,
+    pub creation_fee: u64,
+    pub gas_deposit: u64,
+    pub platform_fee: u64,
+    pub referral_bonus: u64,
+    pub referrer_bonus_cap: u64,
+    pub sell_limit_percent: u8,
+    pub guardian: Pubkey,
+    pub unpause_delay: i64,
+}
+
+impl ProgramConfigParams {
+    pub const SPACE: usize = 32 + // admin
+        32 + // cpt_mint
+        32 + // token_program
+        1 + // pool_burn_rate
+        8 + // submission_fee
+        8 + // creation_fee
+        8 + // gas_deposit
+        8 + // platform_fee
+        8 + // referral_bonus
+        8 + // referrer_bonus_cap
+        1 + // sell_limit_percent
+        32 + // guardian
+        8; // unpause_delay
+
+    // Checked on queue and again on apply
+    fn validate(&self) -> Result<()> {
+        require!(self.admin != Pubkey::default(), TokenError::InvalidConfig);
+        require!(self.pool_burn_rate <= MAX_POOL_BURN_RATE, TokenError::InvalidConfig);
+        require!(
+            self.sell_limit_percent > 0 && self.sell_limit_percent <= MAX_SELL_LIMIT_PERCENT,
+            TokenError::InvalidConfig
+        );
+        require!(
+            (0..=MAX_UNPAUSE_DELAY).contains(&self.unpause_delay),
+            TokenError::InvalidConfig
+        );
+        Ok(())
+    }
+}
+
+// At most one queued change at a time
+#[account]
+pub struct PendingConfigUpdate {
+    pub params: ProgramConfigParams,
+    pub queued_by: Pubkey, // Gets the rent back when the change is applied or cancelled
+    pub queued_at: i64,
+    pub eligible_at: i64,
+}
+
+impl PendingConfigUpdate {
+    pub const SPACE: usize = 8 + // discriminator
+        ProgramConfigParams::SPACE + // params
+        32 + // queued_by
+        8 + // queued_at
+        8; // eligible_at
+}
+
+#[event]
+pub struct ConfigUpdateQueued {
+    pub params: ProgramConfigParams,
+    pub queued_by: Pubkey,
+    pub eligible_at: i64,
+}
+
+// Created once by the deployer recorded in TokenState, who names the first admin in params
+#[derive(Accounts)]
+pub struct InitializeProgramConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"token_state"],
+        bump,
+        has_one = authority @ TokenError::Unauthorized,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ProgramConfig::SPACE,
+        seeds = [b"program_config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QueueConfigUpdate<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PendingConfigUpdate::SPACE,
+        seeds = [b"pending_config_update"],
+        bump
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigUpdate<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_config_update"],
+        bump,
+        close = queued_by,
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    /// CHECK: Receives the rent, must be the key that queued the change
+    #[account(mut, address = pending_config_update.queued_by @ TokenError::InvalidConfig)]
+    pub queued_by: UncheckedAccount<'info>,
+}
+
+// Permissionless once the delay has passed
+#[derive(Accounts)]
+pub struct ApplyConfigUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_config_update"],
+        bump,
+        close = queued_by,
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    /// CHECK: Receives the rent, must be the key that queued the change
+    #[account(mut, address = pending_config_update.queued_by @ TokenError::InvalidConfig)]
+    pub queued_by: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GuardProgram<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump,
+        has_one = guardian @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn initialize_program_config(
+    ctx: Context<InitializeProgramConfig>,
+    params: ProgramConfigParams,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.paused = 0;
+    program_config.unpause_eligible_at = [0; INSTRUCTION_FAMILY_COUNT];
+    program_config.apply(params)?;
+
+    msg!("Program config initialized, admin {}", program_config.admin);
+    msg!("Guardian {}, unpause delay {}s", program_config.guardian, program_config.unpause_delay);
+    Ok(())
+}
+
+pub fn queue_config_update(
+    ctx: Context<QueueConfigUpdate>,
+    params: ProgramConfigParams,
+) -> Result<()> {
+    params.validate()?;
+
+    let queued_at = Clock::get()?.unix_timestamp;
+    let eligible_at = queued_at
+        .checked_add(CONFIG_UPDATE_DELAY)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    let queued_by = ctx.accounts.admin.key();
+
+    let pending = &mut ctx.accounts.pending_config_update;
+    pending.params = params.clone();
+    pending.queued_by = queued_by;
+    pending.queued_at = queued_at;
+    pending.eligible_at = eligible_at;
+
+    emit!(ConfigUpdateQueued { params, queued_by, eligible_at });
+
+    msg!("Program config update queued, eligible at {}", eligible_at);
+    Ok(())
+}
+
+pub fn cancel_config_update(_ctx: Context<CancelConfigUpdate>) -> Result<()> {
+    msg!("Queued program config update cancelled");
+    Ok(())
+}
+
+pub fn apply_config_update(ctx: Context<ApplyConfigUpdate>) -> Result<()> {
+    let pending = &ctx.accounts.pending_config_update;
+    require!(
+        Clock::get()?.unix_timestamp >= pending.eligible_at,
+        TokenError::ConfigUpdateNotReady
+    );
+
+    ctx.accounts.program_config.apply(pending.params.clone())?;
+
+    msg!("Program config update applied");
+    Ok(())
+}
+
+// Takes effect immediately and cancels any pending unpause of the family
+pub fn pause_family(ctx: Context<GuardProgram>, family: InstructionFamily) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.paused |= family.bit();
+    program_config.unpause_eligible_at[family as usize] = 0;
+
+    msg!("{:?} paused", family);
+    Ok(())
+}
+
+// Unpauses right away without a delay, otherwise starts the delay for execute_unpause
+pub fn unpause_family(ctx: Context<GuardProgram>, family: InstructionFamily) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    require!(program_config.is_paused(family), TokenError::NotPaused);
+
+    if program_config.unpause_delay == 0 {
+        program_config.set_unpaused(family);
+        msg!("{:?} unpaused", family);
+        return Ok(());
+    }
+
+    let eligible_at = Clock::get()?.unix_timestamp
+        .checked_add(program_config.unpause_delay)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    program_config.unpause_eligible_at[family as usize] = eligible_at;
+
+    msg!("{:?} unpause requested, eligible at {}", family, eligible_at);
+    Ok(())
+}
+
+pub fn execute_unpause(ctx: Context<GuardProgram>, family: InstructionFamily) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    let eligible_at = program_config.unpause_eligible_at[family as usize];
+    require!(eligible_at != 0, TokenError::NoPendingUnpause);
+    require!(Clock::get()?.unix_timestamp >= eligible_at, TokenError::UnpauseNotReady);
+
+    program_config.set_unpaused(family);
+
+    msg!("{:?} unpaused", family);
+    Ok(())
+}
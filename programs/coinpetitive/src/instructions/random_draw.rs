@@ -0,0 +1,299 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+
+use crate::instructions::program_config::ProgramConfig;
+use crate::instructions::token_errors::TokenError;
+
+/*
+    commit-reveal randomness for random airdrops
+    commit: the admin posts sha256(seed), a hash of the eligibility list published
+            off-chain and how many winners to draw; the draw is tied to the hash of
+            a slot DRAW_SLOT_DELAY slots in the future, unknown at commit time
+    reveal: once that slot has passed, anyone holding the seed reveals it
+            slot hash = hash of the first slot at or after the target slot, so a
+            skipped target slot falls through to the next produced one
+            randomness = sha256(seed || slot hash || draw_id)
+            winner i is the eligibility list index sha256(randomness || i) mod eligible_count,
+            rehashing on duplicates
+    expire: SlotHashes only keeps the last 512 slots, so the reveal has to land within
+            DRAW_REVEAL_WINDOW slots of the target; after that anyone can expire the
+            draw, leaving a public on-chain record of a withheld reveal
+*/
+
+pub const DRAW_SLOT_DELAY: u64 = 10;
+pub const MAX_DRAW_WINNERS: u32 = 50;
+pub const DRAW_REVEAL_WINDOW: u64 = 512; // Slots after the target slot, the SlotHashes depth
+
+#[account]
+pub struct RandomDraw {
+    pub draw_id: u64,
+    pub seed_hash: [u8; 32],
+    pub eligibility_hash: [u8; 32], // Hash of the eligibility list, published off-chain
+    pub eligible_count: u32,
+    pub winner_count: u32,
+    pub target_slot: u64, // Slot whose hash is mixed into the seed
+    pub revealed: bool,
+    pub expired: bool, // Set when the reveal window passed without a reveal
+    pub hashed_slot: u64, // Slot whose hash was actually used, at or after target_slot
+    pub randomness: [u8; 32],
+    pub winners: Vec<u32>, // Indexes into the eligibility list
+}
+
+impl RandomDraw {
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // draw_id
+        32 + // seed_hash
+        32 + // eligibility_hash
+        4 + // eligible_count
+        4 + // winner_count
+        8 + // target_slot
+        1 + // revealed
+        1 + // expired
+        8 + // hashed_slot
+        32 + // randomness
+        4 + (4 * MAX_DRAW_WINNERS as usize); // winners
+
+    pub fn reveal_deadline(&self) -> Result<u64> {
+        let deadline = self.target_slot
+            .checked_add(DRAW_REVEAL_WINDOW)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(deadline)
+    }
+}
+
+#[event]
+pub struct RandomDrawExpired {
+    pub draw_id: u64,
+    pub target_slot: u64,
+    pub expired_at_slot: u64,
+    pub expired_by: Pubkey,
+}
+
+// Deterministic winner indexes, recomputable by anyone from the revealed randomness
+pub fn select_winners(randomness: &[u8; 32], eligible_count: u32, winner_count: u32) -> Vec<u32> {
+    let mut winners = Vec::with_capacity(winner_count as usize);
+    let mut counter: u32 = 0;
+    while winners.len() < winner_count as usize {
+        let hash = hashv(&[randomness, &counter.to_le_bytes()]).to_bytes();
+        let mut value = [0u8; 8];
+        value.copy_from_slice(&hash[..8]);
+        let index = (u64::from_le_bytes(value) % eligible_count as u64) as u32;
+        if !winners.contains(&index) {
+            winners.push(index);
+        }
+        counter += 1;
+    }
+    winners
+}
+
+// SlotHashes is too large to deserialize, so scan the raw entries: u64 length, then
+// (slot, hash) pairs, newest first. Returns the first slot at or after `slot` and its hash.
+fn find_slot_hash(data: &[u8], slot: u64) -> Option<(u64, [u8; 32])> {
+    let len = u64::from_le_bytes(data.get(..8)?.try_into().ok()?) as usize;
+    data.get(8..)?
+        .chunks_exact(40)
+        .take(len)
+        .map(|entry| (u64::from_le_bytes(entry[..8].try_into().unwrap()), entry[8..].try_into().unwrap()))
+        .take_while(|(entry_slot, _)| *entry_slot >= slot)
+        .last()
+}
+
+#[derive(Accounts)]
+#[instruction(draw_id: u64)]
+pub struct CommitRandomDraw<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = RandomDraw::SPACE,
+        seeds = [b"random_draw", draw_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub random_draw: Account<'info, RandomDraw>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Permissionless, the seed is the credential
+#[derive(Accounts)]
+pub struct RevealRandomDraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"random_draw", random_draw.draw_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub random_draw: Account<'info, RandomDraw>,
+
+    /// CHECK: SlotHashes sysvar, parsed by hand
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+// Permissionless, anyone can record a draw whose reveal was withheld
+#[derive(Accounts)]
+pub struct ExpireRandomDraw<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"random_draw", random_draw.draw_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub random_draw: Account<'info, RandomDraw>,
+}
+
+pub fn commit_random_draw(
+    ctx: Context<CommitRandomDraw>,
+    draw_id: u64,
+    seed_hash: [u8; 32],
+    eligibility_hash: [u8; 32],
+    eligible_count: u32,
+    winner_count: u32,
+) -> Result<()> {
+    require!(
+        winner_count > 0 && winner_count <= MAX_DRAW_WINNERS && winner_count <= eligible_count,
+        TokenError::InvalidRandomDraw
+    );
+
+    let target_slot = Clock::get()?.slot
+        .checked_add(DRAW_SLOT_DELAY)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+
+    let draw = &mut ctx.accounts.random_draw;
+    draw.draw_id = draw_id;
+    draw.seed_hash = seed_hash;
+    draw.eligibility_hash = eligibility_hash;
+    draw.eligible_count = eligible_count;
+    draw.winner_count = winner_count;
+    draw.target_slot = target_slot;
+    draw.revealed = false;
+    draw.expired = false;
+    draw.hashed_slot = 0;
+    draw.randomness = [0; 32];
+    draw.winners = Vec::new();
+
+    msg!("Random draw {} committed: {} winners out of {}", draw_id, winner_count, eligible_count);
+    msg!("Target slot {}", target_slot);
+    Ok(())
+}
+
+pub fn reveal_random_draw(ctx: Context<RevealRandomDraw>, seed: [u8; 32]) -> Result<()> {
+    let draw = &mut ctx.accounts.random_draw;
+    require!(!draw.revealed, TokenError::DrawAlreadyRevealed);
+    require!(!draw.expired, TokenError::DrawExpired);
+    let current_slot = Clock::get()?.slot;
+    require!(current_slot > draw.target_slot, TokenError::DrawNotReady);
+    // Past the window the first hash at or after the target is no longer fixed
+    require!(current_slot <= draw.reveal_deadline()?, TokenError::DrawExpired);
+    require!(hashv(&[&seed]).to_bytes() == draw.seed_hash, TokenError::InvalidSeed);
+
+    let (hashed_slot, slot_hash) = {
+        let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+        find_slot_hash(&data, draw.target_slot).ok_or(TokenError::SlotHashUnavailable)?
+    };
+
+    let randomness = hashv(&[&seed, &slot_hash, &draw.draw_id.to_le_bytes()]).to_bytes();
+    draw.hashed_slot = hashed_slot;
+    draw.randomness = randomness;
+    draw.winners = select_winners(&randomness, draw.eligible_count, draw.winner_count);
+    draw.revealed = true;
+
+    msg!("Random draw {} revealed with the hash of slot {}", draw.draw_id, hashed_slot);
+    msg!("Winner indexes: {:?}", draw.winners);
+    Ok(())
+}
+
+pub fn expire_random_draw(ctx: Context<ExpireRandomDraw>) -> Result<()> {
+    let draw = &mut ctx.accounts.random_draw;
+    require!(!draw.revealed, TokenError::DrawAlreadyRevealed);
+    require!(!draw.expired, TokenError::DrawExpired);
+    let current_slot = Clock::get()?.slot;
+    require!(current_slot > draw.reveal_deadline()?, TokenError::DrawStillRevealable);
+
+    draw.expired = true;
+
+    emit!(RandomDrawExpired {
+        draw_id: draw.draw_id,
+        target_slot: draw.target_slot,
+        expired_at_slot: current_slot,
+        expired_by: ctx.accounts.caller.key(),
+    });
+    msg!("Random draw {} expired without a reveal", draw.draw_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SlotHashes layout, entries newest first
+    fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[*slot as u8; 32]);
+        }
+        data
+    }
+
+    #[test]
+    fn winners_are_distinct_and_inside_the_eligibility_list() {
+        let randomness = hashv(&[b"seed"]).to_bytes();
+        let winners = select_winners(&randomness, 20, 10);
+
+        assert_eq!(winners.len(), 10);
+        assert!(winners.iter().all(|index| *index < 20));
+        for (i, winner) in winners.iter().enumerate() {
+            assert!(!winners[..i].contains(winner));
+        }
+    }
+
+    #[test]
+    fn winners_are_recomputable_from_the_randomness() {
+        let randomness = hashv(&[b"seed"]).to_bytes();
+        let other = hashv(&[b"other seed"]).to_bytes();
+
+        assert_eq!(select_winners(&randomness, 1_000, 5), select_winners(&randomness, 1_000, 5));
+        assert_ne!(select_winners(&randomness, 1_000, 5), select_winners(&other, 1_000, 5));
+    }
+
+    #[test]
+    fn drawing_everyone_selects_every_index_once() {
+        let randomness = hashv(&[b"seed"]).to_bytes();
+        let mut winners = select_winners(&randomness, 7, 7);
+        winners.sort();
+
+        assert_eq!(winners, vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn the_target_slot_hash_is_used_when_present() {
+        let data = slot_hashes(&[105, 104, 103, 102]);
+
+        assert_eq!(find_slot_hash(&data, 103), Some((103, [103; 32])));
+    }
+
+    #[test]
+    fn a_skipped_target_slot_falls_through_to_the_next_produced_slot() {
+        let data = slot_hashes(&[106, 105, 102, 101]);
+
+        assert_eq!(find_slot_hash(&data, 103), Some((105, [105; 32])));
+    }
+
+    #[test]
+    fn no_slot_hash_at_or_after_the_target_is_none() {
+        let data = slot_hashes(&[102, 101]);
+
+        assert_eq!(find_slot_hash(&data, 103), None);
+    }
+}
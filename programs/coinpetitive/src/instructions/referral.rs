@@ -0,0 +1,270 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::instructions::beneficiary_registry::AllocationCategory;
+use crate::instructions::distribution_ledger::DistributionLedger;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
+use crate::instructions::token_errors::TokenError;
+
+/*
+    affiliate referrals, paid out of the affiliator rewards allocation
+    a referrer registers one code, stored as a PDA keyed by the code itself
+    a referee is bound to a referrer on their first pay_participation_fee whose CPT fee
+    is at least config.submission_fee, and never again
+    the challenge must be created by someone other than the referee and the referrer,
+    so neither can farm the bonus by paying an entry fee back to themselves
+    the binding credits config.referral_bonus to the referrer, up to config.referrer_bonus_cap
+    credited bonuses count against the marketing allocation in the distribution ledger
+    and are claimed from the affiliator vault with claim_referral_bonus
+*/
+
+pub const MAX_REFERRAL_CODE_LEN: usize = 16;
+
+// Code lookup, one per code
+#[account]
+pub struct ReferralCode {
+    pub referrer: Pubkey,
+    pub code: String,
+}
+
+impl ReferralCode {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // referrer
+        4 + MAX_REFERRAL_CODE_LEN; // code
+}
+
+// Referrer accounting, one per wallet
+#[account]
+pub struct Referrer {
+    pub wallet: Pubkey,
+    pub code: String,
+    pub referral_count: u64,
+    pub total_bonus_earned: u64, // Credited so far, capped by config.referrer_bonus_cap
+    pub pending_bonus: u64, // Credited but not yet claimed
+}
+
+impl Referrer {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // wallet
+        4 + MAX_REFERRAL_CODE_LEN + // code
+        8 + // referral_count
+        8 + // total_bonus_earned
+        8; // pending_bonus
+}
+
+// Created on a wallet's first participation, bound on its first qualifying one
+#[account]
+pub struct RefereeRecord {
+    pub referee: Pubkey,
+    pub referrer: Pubkey, // Pubkey::default() when the first participation had no code
+    pub first_participation_at: i64,
+}
+
+// Whether a participation may bind the referee: the fee has to go to an outside creator
+pub fn is_qualifying_participation(creator: Pubkey, referee: Pubkey, referrer: Option<Pubkey>) -> bool {
+    creator != referee && referrer != Some(creator)
+}
+
+impl RefereeRecord {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // referee
+        32 + // referrer
+        8; // first_participation_at
+
+    pub fn is_new(&self) -> bool {
+        self.first_participation_at == 0
+    }
+
+    // Binds the referee and credits the referrer's bonus. Only called on the first
+    // qualifying participation that pays at least config.submission_fee.
+    pub fn bind(
+        &mut self,
+        referee: Pubkey,
+        timestamp: i64,
+        referral: Option<(&ReferralCode, &mut Referrer)>,
+        config: &ProgramConfig,
+        distribution_ledger: &mut DistributionLedger,
+    ) -> Result<()> {
+        self.referee = referee;
+        self.first_participation_at = timestamp;
+
+        let Some((referral_code, referrer)) = referral else {
+            return Ok(());
+        };
+        require!(referrer.wallet == referral_code.referrer, TokenError::InvalidReferralCode);
+        require!(referrer.wallet != referee, TokenError::SelfReferral);
+
+        self.referrer = referrer.wallet;
+        referrer.referral_count = referrer.referral_count
+            .checked_add(1)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+
+        let bonus = config.referral_bonus;
+        if bonus == 0 {
+            return Ok(());
+        }
+
+        let total_bonus_earned = referrer.total_bonus_earned
+            .checked_add(bonus)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        require!(total_bonus_earned <= config.referrer_bonus_cap, TokenError::ReferrerCapReached);
+        require!(
+            distribution_ledger.remaining(AllocationCategory::Marketing) >= bonus,
+            TokenError::AffiliatorAllocationExhausted
+        );
+        distribution_ledger.record(AllocationCategory::Marketing, bonus)?;
+
+        referrer.total_bonus_earned = total_bonus_earned;
+        referrer.pending_bonus = referrer.pending_bonus
+            .checked_add(bonus)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+
+        msg!("Referral bonus of {} credited to {}", bonus, referrer.wallet);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct CreateReferralCode<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        init,
+        payer = wallet,
+        space = ReferralCode::SPACE,
+        seeds = [b"referral_code", code.as_bytes()],
+        bump
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+
+    #[account(
+        init,
+        payer = wallet,
+        space = Referrer::SPACE,
+        seeds = [b"referrer", wallet.key().as_ref()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralBonus<'info> {
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", wallet.key().as_ref()],
+        bump,
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: PDA that owns every program vault, never holds data
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"affiliator_vault"],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub affiliator_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = wallet,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn create_referral_code(ctx: Context<CreateReferralCode>, code: String) -> Result<()> {
+    require!(
+        !code.is_empty()
+            && code.len() <= MAX_REFERRAL_CODE_LEN
+            && code.bytes().all(|b| b.is_ascii_alphanumeric()),
+        TokenError::InvalidReferralCode
+    );
+
+    let wallet = ctx.accounts.wallet.key();
+    let referral_code = &mut ctx.accounts.referral_code;
+    referral_code.referrer = wallet;
+    referral_code.code = code.clone();
+
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.wallet = wallet;
+    referrer.code = code;
+    referrer.referral_count = 0;
+    referrer.total_bonus_earned = 0;
+    referrer.pending_bonus = 0;
+
+    msg!("Referral code {} registered for {}", referrer.code, wallet);
+    Ok(())
+}
+
+pub fn claim_referral_bonus(ctx: Context<ClaimReferralBonus>) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Distribution)?;
+
+    let amount = ctx.accounts.referrer.pending_bonus;
+    require!(amount > 0, TokenError::NothingToClaim);
+
+    let seeds = &["vault_authority".as_bytes(), &[ctx.bumps.vault_authority]];
+    let signer = [&seeds[..]];
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.affiliator_vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            &signer,
+        ),
+        amount,
+    )?;
+
+    ctx.accounts.referrer.pending_bonus = 0;
+
+    msg!("Claimed {} tokens of referral bonus", amount);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_participations_in_outside_challenges_qualify() {
+        let creator = Pubkey::new_unique();
+        let referee = Pubkey::new_unique();
+        let referrer = Pubkey::new_unique();
+
+        assert!(is_qualifying_participation(creator, referee, Some(referrer)));
+        assert!(is_qualifying_participation(creator, referee, None));
+        assert!(!is_qualifying_participation(referee, referee, Some(referrer)));
+        assert!(!is_qualifying_participation(referee, referee, None));
+        assert!(!is_qualifying_participation(referrer, referee, Some(referrer)));
+    }
+}
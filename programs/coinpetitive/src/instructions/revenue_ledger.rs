@@ -0,0 +1,350 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::instructions::attestor_set::{AttestationCluster, AttestorSet};
+use crate::instructions::ed25519::{attestation_message, verified_signers};
+use crate::instructions::program_config::ProgramConfig;
+use crate::instructions::token::TokenState;
+use crate::instructions::token_errors::TokenError;
+
+/*
+    revenue ledger behind the self-sustaining mint milestone
+    program revenue (lamports paid into the revenue treasury PDA) is recorded
+    into the open period as it happens: the creation fee on challenge creation,
+    the platform fee on finalization and creator claims
+    once a period has run for REVENUE_PERIOD_SECONDS the attestors close it with
+    a signed operational cost report
+    the platform is self-sustaining while revenue over the trailing
+    TRAILING_REVENUE_PERIODS closed periods exceeds their reported cost
+    the config admin (the multisig signer once admin is handed to it) withdraws
+    the collected lamports to pay for operations
+*/
+
+pub const REVENUE_PERIOD_SECONDS: i64 = 2_592_000; // 60 * 60 * 24 * 30, 30 days
+pub const TRAILING_REVENUE_PERIODS: usize = 3;
+pub const MAX_REVENUE_PERIODS: usize = 24; // Closed periods kept for review
+
+// Domain of every cost report attestors sign, see ed25519::attestation_message
+pub const COST_REPORT_DOMAIN: &[u8] = b"cpt-cost-report";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevenueSource {
+    CreationFee,
+    PlatformFee,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct RevenuePeriod {
+    pub index: u64,
+    pub start: i64,
+    pub end: i64,
+    pub revenue: u64,
+    pub operational_cost: u64,
+    pub evidence_hash: [u8; 32],
+}
+
+impl RevenuePeriod {
+    pub const SPACE: usize = 8 + // index
+        8 + // start
+        8 + // end
+        8 + // revenue
+        8 + // operational_cost
+        32; // evidence_hash
+}
+
+#[account]
+pub struct RevenueLedger {
+    pub period_index: u64,
+    pub period_start: i64,
+    pub period_revenue: u64,
+    pub total_creation_fees: u64,
+    pub total_platform_fees: u64,
+    pub total_other_revenue: u64,
+    pub total_withdrawn: u64, // Lamports taken out of the revenue treasury
+    pub periods: Vec<RevenuePeriod>, // Closed periods, oldest first
+}
+
+impl RevenueLedger {
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // period_index
+        8 + // period_start
+        8 + // period_revenue
+        8 + // total_creation_fees
+        8 + // total_platform_fees
+        8 + // total_other_revenue
+        8 + // total_withdrawn
+        4 + (RevenuePeriod::SPACE * MAX_REVENUE_PERIODS); // periods
+
+    // Called by every instruction that pays lamports into the revenue treasury
+    pub fn record_revenue(&mut self, source: RevenueSource, amount: u64) -> Result<()> {
+        let total = match source {
+            RevenueSource::CreationFee => &mut self.total_creation_fees,
+            RevenueSource::PlatformFee => &mut self.total_platform_fees,
+            RevenueSource::Other => &mut self.total_other_revenue,
+        };
+        *total = total
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        self.period_revenue = self.period_revenue
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Revenue and cost summed over the most recent closed periods
+    pub fn trailing_totals(&self) -> Result<(u64, u64)> {
+        let skip = self.periods.len().saturating_sub(TRAILING_REVENUE_PERIODS);
+        let mut revenue: u64 = 0;
+        let mut cost: u64 = 0;
+        for period in &self.periods[skip..] {
+            revenue = revenue
+                .checked_add(period.revenue)
+                .ok_or(TokenError::ArithmeticOverflow)?;
+            cost = cost
+                .checked_add(period.operational_cost)
+                .ok_or(TokenError::ArithmeticOverflow)?;
+        }
+        Ok((revenue, cost))
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeRevenueLedger<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = RevenueLedger::SPACE,
+        seeds = [b"revenue_ledger"],
+        bump
+    )]
+    pub revenue_ledger: Box<Account<'info, RevenueLedger>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct RevenueWithdrawn {
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub withdrawn_by: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRevenue<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    // Lamport-only PDA every creation and platform fee is paid into
+    #[account(
+        mut,
+        seeds = [b"revenue_treasury"],
+        bump,
+    )]
+    pub revenue_treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue_ledger"],
+        bump,
+    )]
+    pub revenue_ledger: Box<Account<'info, RevenueLedger>>,
+
+    /// CHECK: only receives lamports
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReportOperationalCost<'info> {
+    pub reporter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"attestor_set"],
+        bump,
+    )]
+    pub attestor_set: Account<'info, AttestorSet>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue_ledger"],
+        bump,
+    )]
+    pub revenue_ledger: Box<Account<'info, RevenueLedger>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    /// CHECK: Instructions sysvar, used to read the ed25519 signature instructions
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+// Bytes each attestor signs for a cost report. The period index makes every
+// report single use, since a period can only be closed once.
+pub fn cost_report_message(
+    cluster: AttestationCluster,
+    period_index: u64,
+    operational_cost: u64,
+    evidence_hash: &[u8; 32],
+) -> Vec<u8> {
+    attestation_message(
+        COST_REPORT_DOMAIN,
+        cluster,
+        &[&period_index.to_le_bytes(), &operational_cost.to_le_bytes(), evidence_hash],
+    )
+}
+
+pub fn initialize_revenue_ledger(ctx: Context<InitializeRevenueLedger>) -> Result<()> {
+    let revenue_ledger = &mut ctx.accounts.revenue_ledger;
+    revenue_ledger.period_index = 0;
+    revenue_ledger.period_start = Clock::get()?.unix_timestamp;
+    revenue_ledger.period_revenue = 0;
+    revenue_ledger.total_creation_fees = 0;
+    revenue_ledger.total_platform_fees = 0;
+    revenue_ledger.total_other_revenue = 0;
+    revenue_ledger.total_withdrawn = 0;
+    revenue_ledger.periods = Vec::new();
+
+    msg!("Revenue ledger initialized");
+    Ok(())
+}
+
+// Closes the open period with the attested operational cost and re-evaluates
+// whether the platform is self-sustaining
+pub fn report_operational_cost(
+    ctx: Context<ReportOperationalCost>,
+    period_index: u64,
+    operational_cost: u64,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let revenue_ledger = &mut ctx.accounts.revenue_ledger;
+
+    require!(
+        period_index == revenue_ledger.period_index,
+        TokenError::InvalidRevenuePeriod
+    );
+    let period_end = revenue_ledger.period_start
+        .checked_add(REVENUE_PERIOD_SECONDS)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    require!(current_timestamp >= period_end, TokenError::RevenuePeriodNotEnded);
+
+    // Enough configured attestors must have signed this exact report
+    let attestor_set = &mut ctx.accounts.attestor_set;
+    let message = cost_report_message(attestor_set.cluster, period_index, operational_cost, &evidence_hash);
+    let signers = verified_signers(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &message,
+        &attestor_set.attestors,
+    )?;
+    require!(
+        signers.len() >= attestor_set.threshold as usize,
+        TokenError::InsufficientAttestations
+    );
+    attestor_set.report_count = attestor_set.report_count
+        .checked_add(1)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+
+    // Close the period, dropping the oldest once the history is full
+    let closed = RevenuePeriod {
+        index: period_index,
+        start: revenue_ledger.period_start,
+        end: current_timestamp,
+        revenue: revenue_ledger.period_revenue,
+        operational_cost,
+        evidence_hash,
+    };
+    if revenue_ledger.periods.len() == MAX_REVENUE_PERIODS {
+        revenue_ledger.periods.remove(0);
+    }
+    revenue_ledger.periods.push(closed);
+
+    let (trailing_revenue, trailing_cost) = revenue_ledger.trailing_totals()?;
+    ctx.accounts.token_state.record_self_sustaining(trailing_revenue > trailing_cost);
+
+    msg!(
+        "Revenue period {} closed: revenue {} cost {}",
+        period_index,
+        revenue_ledger.period_revenue,
+        operational_cost
+    );
+    msg!(
+        "Trailing revenue {} vs cost {}, self-sustaining: {}",
+        trailing_revenue,
+        trailing_cost,
+        ctx.accounts.token_state.is_self_sustaining
+    );
+
+    // Open the next period
+    revenue_ledger.period_index = period_index
+        .checked_add(1)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    revenue_ledger.period_start = current_timestamp;
+    revenue_ledger.period_revenue = 0;
+
+    Ok(())
+}
+
+pub fn withdraw_revenue(ctx: Context<WithdrawRevenue>, amount: u64) -> Result<()> {
+    require!(amount > 0, TokenError::InvalidWithdrawalAmount);
+
+    // The treasury stays rent exempt, everything above that is withdrawable
+    let treasury = &ctx.accounts.revenue_treasury;
+    let available = treasury
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(amount <= available, TokenError::InsufficientRevenue);
+
+    let seeds = &["revenue_treasury".as_bytes(), &[ctx.bumps.revenue_treasury]];
+    let signer = [&seeds[..]];
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: treasury.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+            },
+            &signer,
+        ),
+        amount,
+    )?;
+
+    let revenue_ledger = &mut ctx.accounts.revenue_ledger;
+    revenue_ledger.total_withdrawn = revenue_ledger.total_withdrawn
+        .checked_add(amount)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+
+    emit!(RevenueWithdrawn {
+        amount,
+        destination: ctx.accounts.destination.key(),
+        withdrawn_by: ctx.accounts.admin.key(),
+    });
+    msg!("Withdrew {} lamports of revenue to {}", amount, ctx.accounts.destination.key());
+    msg!("Total withdrawn: {}", revenue_ledger.total_withdrawn);
+    Ok(())
+}
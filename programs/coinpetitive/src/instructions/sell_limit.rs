@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::token_errors::TokenError;
+
+// The 24-hour window is tracked as 24 hourly buckets
+pub const SELL_WINDOW_BUCKETS: usize = 24;
+pub const SELL_BUCKET_SECONDS: i64 = 3_600;
+
+// Rolling 24-hour sell volume, shared with the Token-2022 transfer hook
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug)]
+pub struct SellWindow {
+    pub bucket_volumes: [u64; SELL_WINDOW_BUCKETS],
+    pub last_bucket: i64, // Hour index of the most recent recorded sell
+}
+
+impl SellWindow {
+    pub const SPACE: usize = 8 * SELL_WINDOW_BUCKETS + // bucket_volumes
+        8; // last_bucket
+
+    // Clear the buckets that fell out of the window since the last sell
+    fn roll(&mut self, now_bucket: i64) {
+        let elapsed = now_bucket.saturating_sub(self.last_bucket);
+        if elapsed >= SELL_WINDOW_BUCKETS as i64 {
+            self.bucket_volumes = [0; SELL_WINDOW_BUCKETS];
+        } else {
+            for hour in (self.last_bucket + 1)..=now_bucket {
+                self.bucket_volumes[hour.rem_euclid(SELL_WINDOW_BUCKETS as i64) as usize] = 0;
+            }
+        }
+        self.last_bucket = now_bucket.max(self.last_bucket);
+    }
+
+    pub fn window_volume(&self) -> Result<u64> {
+        self.bucket_volumes
+            .iter()
+            .try_fold(0u64, |total, volume| total.checked_add(*volume))
+            .ok_or(TokenError::ArithmeticOverflow.into())
+    }
+
+    // Add a sell to the window, rejecting it if the 24-hour total would exceed `limit`
+    pub fn record_sell(&mut self, timestamp: i64, amount: u64, limit: u64) -> Result<()> {
+        let now_bucket = timestamp / SELL_BUCKET_SECONDS;
+        self.roll(now_bucket);
+
+        let window_total = self.window_volume()?
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        require!(window_total <= limit, TokenError::ExceedsDailySellLimit);
+
+        let bucket = &mut self.bucket_volumes[now_bucket.rem_euclid(SELL_WINDOW_BUCKETS as i64) as usize];
+        *bucket = bucket
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+// Per-wallet rolling record of outgoing volume
+#[account]
+pub struct SellTracker {
+    pub wallet: Pubkey,
+    pub window: SellWindow,
+}
+
+impl SellTracker {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // wallet
+        SellWindow::SPACE; // window
+}
+
+// Token accounts owned by the program's vault authority are not user wallets
+pub fn is_program_vault(owner: &Pubkey, program_id: &Pubkey) -> bool {
+    let (vault_authority, _) = Pubkey::find_program_address(&[b"vault_authority"], program_id);
+    *owner == vault_authority
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = SELL_BUCKET_SECONDS;
+
+    #[test]
+    fn sells_inside_the_window_add_up_against_the_limit() {
+        let mut window = SellWindow::default();
+        window.record_sell(100 * HOUR, 60, 100).unwrap();
+        window.record_sell(110 * HOUR, 40, 100).unwrap();
+
+        assert_eq!(window.window_volume().unwrap(), 100);
+        assert!(window.record_sell(123 * HOUR, 1, 100).is_err());
+        // A rejected sell leaves the window untouched
+        assert_eq!(window.window_volume().unwrap(), 100);
+    }
+
+    #[test]
+    fn buckets_expire_after_24_hours() {
+        let mut window = SellWindow::default();
+        window.record_sell(100 * HOUR, 60, 100).unwrap();
+        window.record_sell(110 * HOUR, 40, 100).unwrap();
+
+        // The first sell's bucket rolls out exactly 24 hours later
+        window.record_sell(124 * HOUR, 60, 100).unwrap();
+        assert_eq!(window.window_volume().unwrap(), 100);
+
+        window.record_sell(134 * HOUR + HOUR - 1, 0, 100).unwrap();
+        assert_eq!(window.window_volume().unwrap(), 60);
+    }
+
+    #[test]
+    fn a_long_gap_clears_every_bucket() {
+        let mut window = SellWindow::default();
+        for hour in 0..SELL_WINDOW_BUCKETS as i64 {
+            window.record_sell(hour * HOUR, 10, u64::MAX).unwrap();
+        }
+        assert_eq!(window.window_volume().unwrap(), 240);
+
+        window.record_sell(1_000 * HOUR, 5, 5).unwrap();
+        assert_eq!(window.window_volume().unwrap(), 5);
+    }
+
+    #[test]
+    fn an_older_timestamp_does_not_rewind_the_window() {
+        let mut window = SellWindow::default();
+        window.record_sell(100 * HOUR, 50, 100).unwrap();
+        window.record_sell(99 * HOUR, 50, 100).unwrap();
+
+        assert_eq!(window.last_bucket, 100);
+        assert!(window.record_sell(100 * HOUR, 1, 100).is_err());
+    }
+}
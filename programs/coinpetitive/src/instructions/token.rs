@@ -1,21 +1,39 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    metadata::{create_metadata_accounts_v3 , mpl_token_metadata::types::DataV2 , CreateMetadataAccountsV3 , Metadata as Metaplex},
-    token::{mint_to, burn, transfer, Mint, MintTo, Burn, Transfer, Token, TokenAccount}
+    metadata::{create_metadata_accounts_v3 , update_metadata_accounts_v2 , mpl_token_metadata::types::DataV2 , CreateMetadataAccountsV3 , UpdateMetadataAccountsV2 , Metadata as Metaplex},
+    token::{mint_to, burn, transfer, set_authority, spl_token::instruction::AuthorityType, Mint, MintTo, Burn, Transfer, SetAuthority, Token, TokenAccount},
+    token_2022::{spl_token_2022::instruction::AuthorityType as AuthorityType2022, Token2022},
+    token_interface::{set_authority as set_authority_2022, Mint as Mint2022, SetAuthority as SetAuthority2022},
 };
 
 // Import constants from token_constants.rs
 use crate::instructions::token_constants::{
-    INITIAL_SUPPLY,
     MAX_SUPPLY,
     MINT_INCREMENT,
-    BURN_RATE,
-    MIN_TIME_BETWEEN_MINTS
+    MIN_TIME_BETWEEN_MINTS,
+    CHALLENGES_MILESTONE_1,
+    CHALLENGES_MILESTONE_2,
+    ENTRY_FEES_MILESTONE_1,
+    ENTRY_FEES_MILESTONE_2,
+    WALLETS_MILESTONE_1,
+    WALLETS_MILESTONE_2,
+    WALLETS_MILESTONE_3,
+    MILESTONE_CHALLENGES_1,
+    MILESTONE_CHALLENGES_2,
+    MILESTONE_ENTRY_FEES_1,
+    MILESTONE_ENTRY_FEES_2,
+    MILESTONE_WALLETS_1,
+    MILESTONE_WALLETS_2,
+    MILESTONE_WALLETS_3,
+    MILESTONE_SELF_SUSTAINING,
 };
 
 // Import error type from token_errors.rs
 use crate::instructions::token_errors::TokenError;
+use crate::instructions::emission::EmissionLedger;
+use crate::instructions::sell_limit::{SellTracker, is_program_vault};
+use crate::instructions::pool_registry::PoolRegistry;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
 
 
 #[derive(Accounts)]
@@ -59,25 +77,39 @@ pub struct MintTokens<'info> {
         mint::authority = mint,
     )]
     pub mint: Account<'info, Mint>,
+    /// CHECK: PDA that owns every program vault, never holds data
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = mint,
-        associated_token::authority = payer,
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    // New supply always lands in the program-owned emission reserve
+    #[account(
+        mut,
+        seeds = [b"emission_reserve"],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub emission_reserve: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"emission_ledger"],
+        bump,
     )]
-    pub destination: Account<'info, TokenAccount>,
+    pub emission_ledger: Account<'info, EmissionLedger>,
     #[account(
         mut,
         seeds = [b"token_state"],
         bump,
     )]
     pub token_state: Account<'info, TokenState>,
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    pub rent: Sysvar<'info, Rent>,
-    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
@@ -88,6 +120,39 @@ pub struct InitTokenParams {
     pub decimals: u8,
 }
 
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    /// CHECK: Metaplex metadata PDA of the mint
+    pub metadata: UncheckedAccount<'info>,
+    pub token_metadata_program: Program<'info, Metaplex>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct UpdateTokenMetadataParams {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub make_immutable: bool, // Set once branding is final, cannot be undone
+}
+
 #[account]
 #[derive(Default, Debug)]
 pub struct TokenState {
@@ -101,6 +166,14 @@ pub struct TokenState {
     pub is_self_sustaining: bool, // Track if platform is self-sustaining
     pub last_challenge_tracked: i64, // Add this field
     pub pending_mint_milestone: Option<u8>, // Store which milestone triggers the next mint
+    pub pending_mint_queue: Vec<u8>, // Met-but-unminted milestones, in the order they were met
+    pub genesis_completed: bool, // Set once the 21M launch supply has been distributed
+    pub genesis_timestamp: i64,
+    pub authority: Pubkey, // Deployer, allowed to create the program config
+    pub token_2022_mint: Pubkey, // Token-2022 CPT mint with the transfer hook, once created
+    pub migrated_supply: u64, // Legacy supply swapped onto the Token-2022 mint
+    pub total_minted: u64, // Everything ever minted, counts against the 61M cap
+    pub total_burned: u64, // Everything ever burned, current_supply = total_minted - total_burned
 }
 
 impl TokenState {
@@ -114,36 +187,245 @@ impl TokenState {
         8 + // mint_conditions_met (8 booleans)
          8 + // last_challenge_tracked
          1 + // is_self_sustaining boolean
-         1; // pending_mint_milestone (Option<u8>)
+         2 + // pending_mint_milestone (Option<u8>)
+         4 + 8 + // pending_mint_queue (at most 8 milestones)
+         1 + // genesis_completed
+         8 + // genesis_timestamp
+         32 + // authority
+         32 + // token_2022_mint
+         8 + // migrated_supply
+         8 + // total_minted
+         8; // total_burned
+
+    pub fn mark_milestone_met(&mut self, milestone: usize) {
+        if !self.mint_conditions_met[milestone] {
+            self.mint_conditions_met[milestone] = true;
+            // Queue the mint so a milestone met inside the cooldown is delayed, not lost
+            self.pending_mint_queue.push(milestone as u8);
+            self.pending_mint_milestone = self.pending_mint_queue.first().copied();
+            msg!("Mint milestone {} reached and queued", milestone);
+        }
+    }
+
+    // Every mint goes through here so the cap applies to lifetime issuance,
+    // burned tokens never free up room under it
+    pub fn record_mint(&mut self, amount: u64) -> Result<()> {
+        let total_minted = self.total_minted
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        require!(total_minted <= MAX_SUPPLY, TokenError::ExceedsMaxSupplyCap);
+
+        self.total_minted = total_minted;
+        self.current_supply = self.current_supply
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Every burn of the CPT mint goes through here so current_supply tracks mint.supply
+    pub fn record_burn(&mut self, amount: u64) -> Result<()> {
+        self.current_supply = self.current_supply
+            .checked_sub(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        self.total_burned = self.total_burned
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Earliest time the next queued milestone can be minted
+    pub fn next_mint_time(&self) -> Result<i64> {
+        Ok(self.last_mint_timestamp
+            .checked_add(MIN_TIME_BETWEEN_MINTS)
+            .ok_or(TokenError::ArithmeticOverflow)?)
+    }
+
+    // Called by finalize_challenge for every completed challenge
+    pub fn record_challenge_completed(&mut self, timestamp: i64) -> Result<()> {
+        self.challenges_completed = self.challenges_completed
+            .checked_add(1)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        self.last_challenge_tracked = timestamp;
+
+        if self.challenges_completed >= CHALLENGES_MILESTONE_1 {
+            self.mark_milestone_met(MILESTONE_CHALLENGES_1);
+        }
+        if self.challenges_completed >= CHALLENGES_MILESTONE_2 {
+            self.mark_milestone_met(MILESTONE_CHALLENGES_2);
+        }
+        Ok(())
+    }
+
+    // Called by pay_participation_fee for every entry fee paid
+    pub fn record_entry_fee(&mut self, amount: u64) -> Result<()> {
+        self.total_entry_fees = self.total_entry_fees
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+
+        if self.total_entry_fees >= ENTRY_FEES_MILESTONE_1 {
+            self.mark_milestone_met(MILESTONE_ENTRY_FEES_1);
+        }
+        if self.total_entry_fees >= ENTRY_FEES_MILESTONE_2 {
+            self.mark_milestone_met(MILESTONE_ENTRY_FEES_2);
+        }
+        Ok(())
+    }
+
+    // Called once per HolderRecord confirmed after the minimum holding age
+    pub fn record_new_holder(&mut self) -> Result<()> {
+        self.unique_wallets = self.unique_wallets
+            .checked_add(1)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+
+        if self.unique_wallets >= WALLETS_MILESTONE_1 {
+            self.mark_milestone_met(MILESTONE_WALLETS_1);
+        }
+        if self.unique_wallets >= WALLETS_MILESTONE_2 {
+            self.mark_milestone_met(MILESTONE_WALLETS_2);
+        }
+        if self.unique_wallets >= WALLETS_MILESTONE_3 {
+            self.mark_milestone_met(MILESTONE_WALLETS_3);
+        }
+        Ok(())
+    }
+
+    // Called when a confirmed HolderRecord is closed below the holder minimum
+    pub fn record_holder_removed(&mut self) -> Result<()> {
+        self.unique_wallets = self.unique_wallets
+            .checked_sub(1)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Called by the revenue ledger every time a period is closed
+    pub fn record_self_sustaining(&mut self, is_self_sustaining: bool) {
+        self.is_self_sustaining = is_self_sustaining;
+
+        if is_self_sustaining {
+            self.mark_milestone_met(MILESTONE_SELF_SUSTAINING);
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct RetireMintAuthority<'info> {
+    // Anyone once the cap is reached, but only the deployer or the config admin can lock metadata
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump,
+        mint::authority = mint,
+    )]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Account<'info, TokenState>,
+    // Required once the Token-2022 mint exists, so both mints are retired together
+    #[account(
+        mut,
+        seeds = [b"mint_2022"],
+        bump,
+        mint::token_program = token_2022_program,
+    )]
+    pub mint_2022: Option<Box<InterfaceAccount<'info, Mint2022>>>,
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    /// CHECK: Metaplex metadata PDA of the mint, only touched when locking
+    pub metadata: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+    pub token_metadata_program: Program<'info, Metaplex>,
 }
 
 // Burn tokens struct
 #[derive(Accounts)]
 pub struct BurnTokens<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump,
+    )]
     pub mint: Account<'info, Mint>,
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = authority,
+        token::mint = mint,
+        token::authority = authority,
     )]
     pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Account<'info, TokenState>,
+    // Lifetime burn total of the signing wallet
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = BurnRecord::SPACE,
+        seeds = [b"burn_record", authority.key().as_ref()],
+        bump,
+    )]
+    pub burn_record: Account<'info, BurnRecord>,
+    #[account(mut)]
     pub authority: Signer<'info>,
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct BurnRecord {
+    pub wallet: Pubkey,
+    pub total_burned: u64,
+    pub burn_count: u64,
+    pub last_burn_timestamp: i64,
+}
+
+impl BurnRecord {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // wallet
+        8 + // total_burned
+        8 + // burn_count
+        8; // last_burn_timestamp
 }
 
 // Struct for daily sell limit tracking
 #[derive(Accounts)]
 pub struct TransferWithLimit<'info> {
+    #[account(mut)]
     pub authority: Signer<'info>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
     pub from: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
     pub to: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    // Only the CPT mint, so the pool burn always lands in TokenState
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump,
+    )]
     pub mint: Account<'info, Mint>,
     
     #[account(
@@ -153,7 +435,30 @@ pub struct TransferWithLimit<'info> {
     )]
     pub token_state: Account<'info, TokenState>,
     
+    // Rolling 24-hour sell volume of the wallet that owns `from`
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SellTracker::SPACE,
+        seeds = [b"sell_tracker", from.owner.as_ref()],
+        bump,
+    )]
+    pub sell_tracker: Account<'info, SellTracker>,
+    
+    #[account(
+        seeds = [b"pool_registry"],
+        bump,
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 
@@ -201,22 +506,29 @@ pub fn initiate_token(ctx: Context<InitToken>, metadata: InitTokenParams) -> Res
     token_state.is_self_sustaining = false;
     token_state.last_challenge_tracked = 0; // Initialize last challenge tracked
     token_state.pending_mint_milestone = None; // Initialize pending mint milestone
+    token_state.pending_mint_queue = Vec::new();
+    token_state.genesis_completed = false;
+    token_state.genesis_timestamp = 0;
+    token_state.authority = ctx.accounts.payer.key();
+    token_state.token_2022_mint = Pubkey::default();
+    token_state.migrated_supply = 0;
+    token_state.total_minted = 0;
+    token_state.total_burned = 0;
     
     msg!("Token mint and state initialized successfully.");
     Ok(())
 }
 
-pub fn mint_tokens(ctx: Context<MintTokens>, quantity: u64) -> Result<()> {
+// Mints one increment for the milestone at the head of the queue
+fn mint_pending_milestone(ctx: Context<MintTokens>, current_timestamp: i64) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Minting)?;
+    
     let token_state = &mut ctx.accounts.token_state;
-    let current_timestamp = Clock::get()?.unix_timestamp;
     
-    // Only maintain the max supply cap check for safety
-    require!(
-        token_state.current_supply + quantity <= MAX_SUPPLY,
-        TokenError::ExceedsMaxSupplyCap
-    );
+    require!(!token_state.pending_mint_queue.is_empty(), TokenError::NoMintConditionsMet);
+    
+    token_state.record_mint(MINT_INCREMENT)?;
     
-    // Perform the mint - no conditions checked
     let seeds = &["mint".as_bytes(), &[ctx.bumps.mint]];
     let signer = [&seeds[..]];
     mint_to(
@@ -224,25 +536,190 @@ pub fn mint_tokens(ctx: Context<MintTokens>, quantity: u64) -> Result<()> {
             ctx.accounts.token_program.to_account_info(),
             MintTo {
                 authority: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.destination.to_account_info(),
+                to: ctx.accounts.emission_reserve.to_account_info(),
                 mint: ctx.accounts.mint.to_account_info(),
             },
             &signer,
         ),
-        quantity,
+        MINT_INCREMENT,
     )?;
     
-    // Update state
-    token_state.current_supply += quantity;
+    // Consume the milestone and update state
+    let milestone = token_state.pending_mint_queue.remove(0);
+    token_state.pending_mint_milestone = token_state.pending_mint_queue.first().copied();
+    token_state.mint_conditions_used[milestone as usize] = true;
     token_state.last_mint_timestamp = current_timestamp;
     
-    msg!("Tokens minted successfully: {}", quantity);
+    let emission_ledger = &mut ctx.accounts.emission_ledger;
+    emission_ledger.total_emitted = emission_ledger.total_emitted
+        .checked_add(MINT_INCREMENT)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    
+    msg!("Milestone {} used for mint", milestone);
+    msg!("Tokens minted successfully: {}", MINT_INCREMENT);
     msg!("Current supply: {}", token_state.current_supply);
     Ok(())
 }
 
-// Burn tokens function (for the 1% transaction fee)
+// Every mint event is exactly one MINT_INCREMENT, so there is no amount to pass
+pub fn mint_tokens(ctx: Context<MintTokens>) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    
+    // At most one mint event per 12-month period
+    require!(
+        current_timestamp >= ctx.accounts.token_state.next_mint_time()?,
+        TokenError::MintingTooFrequent
+    );
+    
+    mint_pending_milestone(ctx, current_timestamp)
+}
+
+// Permissionless crank: mints the next queued milestone once the cooldown has passed.
+// Returns the next time a mint can happen.
+pub fn execute_pending_mint(ctx: Context<MintTokens>) -> Result<i64> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    
+    require!(
+        !ctx.accounts.token_state.pending_mint_queue.is_empty(),
+        TokenError::NoMintConditionsMet
+    );
+    
+    let next_mint_time = ctx.accounts.token_state.next_mint_time()?;
+    if current_timestamp < next_mint_time {
+        msg!("Next pending mint is eligible at {}", next_mint_time);
+        return Ok(next_mint_time);
+    }
+    
+    mint_pending_milestone(ctx, current_timestamp)?;
+    
+    Ok(current_timestamp
+        .checked_add(MIN_TIME_BETWEEN_MINTS)
+        .ok_or(TokenError::ArithmeticOverflow)?)
+}
+
+pub fn update_token_metadata(
+    ctx: Context<UpdateTokenMetadata>,
+    params: UpdateTokenMetadataParams
+) -> Result<()> {
+    let seeds = &["mint".as_bytes(), &[ctx.bumps.mint]];
+    let signer = [&seeds[..]];
+    
+    let token_data: DataV2 = DataV2 {
+        name: params.name,
+        symbol: params.symbol,
+        uri: params.uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+    
+    let is_mutable = if params.make_immutable { Some(false) } else { None };
+    
+    update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            UpdateMetadataAccountsV2 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                update_authority: ctx.accounts.mint.to_account_info(),
+            },
+            &signer,
+        ),
+        None,
+        Some(token_data),
+        None,
+        is_mutable,
+    )?;
+    
+    msg!("Token metadata updated");
+    if params.make_immutable {
+        msg!("Token metadata locked as immutable");
+    }
+    Ok(())
+}
+
+// Once the 61M cap is reached, drop the mint authority for good so the cap
+// is visible on the mint account itself
+pub fn retire_mint_authority(ctx: Context<RetireMintAuthority>, lock_metadata: bool) -> Result<()> {
+    require!(
+        ctx.accounts.token_state.total_minted == MAX_SUPPLY,
+        TokenError::MaxSupplyNotReached
+    );
+    
+    let seeds = &["mint".as_bytes(), &[ctx.bumps.mint]];
+    let signer = [&seeds[..]];
+    
+    // The mint PDA is still the metadata update authority, so lock first
+    if lock_metadata {
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == ctx.accounts.token_state.authority
+                || authority == ctx.accounts.program_config.admin,
+            TokenError::Unauthorized
+        );
+        
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    update_authority: ctx.accounts.mint.to_account_info(),
+                },
+                &signer,
+            ),
+            None,
+            None,
+            None,
+            Some(false),
+        )?;
+        msg!("Token metadata locked as immutable");
+    }
+    
+    set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.mint.to_account_info(),
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+            },
+            &signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+    
+    // The Token-2022 mint could otherwise keep minting past the cap
+    if ctx.accounts.token_state.token_2022_mint != Pubkey::default() {
+        let (Some(mint_2022), Some(token_2022_program)) =
+            (&ctx.accounts.mint_2022, &ctx.accounts.token_2022_program)
+        else {
+            return err!(TokenError::InvalidTokenMint);
+        };
+        let seeds = &["mint_2022".as_bytes(), &[ctx.bumps.mint_2022.unwrap()]];
+        let signer = [&seeds[..]];
+        set_authority_2022(
+            CpiContext::new_with_signer(
+                token_2022_program.to_account_info(),
+                SetAuthority2022 {
+                    current_authority: mint_2022.to_account_info(),
+                    account_or_mint: mint_2022.to_account_info(),
+                },
+                &signer,
+            ),
+            AuthorityType2022::MintTokens,
+            None,
+        )?;
+        msg!("Token-2022 mint authority retired");
+    }
+    
+    msg!("Mint authority retired after minting {}", ctx.accounts.token_state.total_minted);
+    Ok(())
+}
+
+// Voluntary burn, open to any holder
 pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
+    require!(amount > 0, TokenError::InvalidBurnAmount);
+    
     burn(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -255,21 +732,43 @@ pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
         amount,
     )?;
     
+    ctx.accounts.token_state.record_burn(amount)?;
+    
+    let burn_record = &mut ctx.accounts.burn_record;
+    burn_record.wallet = ctx.accounts.authority.key();
+    burn_record.total_burned = burn_record.total_burned
+        .checked_add(amount)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    burn_record.burn_count = burn_record.burn_count
+        .checked_add(1)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    burn_record.last_burn_timestamp = Clock::get()?.unix_timestamp;
+    
     msg!("Burned {} tokens", amount);
+    msg!("Wallet total burned: {}", burn_record.total_burned);
+    msg!("Total burned: {}", ctx.accounts.token_state.total_burned);
     Ok(())
 }
 
 
 pub fn transfer_with_limit(ctx: Context<TransferWithLimit>, amount: u64) -> Result<()> {
-    // Calculate 1% of total supply as daily limit
-    let circulating_supply = ctx.accounts.mint.supply;
-    let daily_limit = circulating_supply / 100; // 1% of supply
+    let registry = &ctx.accounts.pool_registry;
+    let is_sell = registry.is_pool(&ctx.accounts.to.key());
+    let is_pool_trade = is_sell || registry.is_pool(&ctx.accounts.from.key());
     
-    // Check against the daily limit
-    require!(amount <= daily_limit, TokenError::ExceedsDailySellLimit);
+    // Selling into a pool counts against the wallet's limit.
+    // Program vaults move funds through their own paths and are not subject to it.
+    if is_sell && !is_program_vault(&ctx.accounts.from.owner, ctx.program_id) {
+        // config.sell_limit_percent of circulating supply per wallet over a rolling 24 hours
+        let daily_limit = ctx.accounts.program_config.daily_sell_limit(ctx.accounts.mint.supply);
+        let sell_tracker = &mut ctx.accounts.sell_tracker;
+        sell_tracker.wallet = ctx.accounts.from.owner;
+        sell_tracker.window.record_sell(Clock::get()?.unix_timestamp, amount, daily_limit)?;
+    }
     
-    // Calculate burn amount (1% for liquidity pool transactions)
-    let burn_amount = amount * BURN_RATE as u64 / 100;
+    // Calculate burn amount (liquidity pool transactions only)
+    let pool_burn_rate = ctx.accounts.program_config.pool_burn_rate;
+    let burn_amount = if is_pool_trade { ctx.accounts.program_config.pool_burn(amount) } else { 0 };
     let transfer_amount = amount - burn_amount;
     
     // Transfer reduced amount to recipient
@@ -300,11 +799,9 @@ pub fn transfer_with_limit(ctx: Context<TransferWithLimit>, amount: u64) -> Resu
         )?;
         
         // Update token state to reflect the burn
-        ctx.accounts.token_state.current_supply = ctx.accounts.token_state.current_supply
-            .checked_sub(burn_amount)
-            .ok_or(TokenError::ArithmeticOverflow)?;
+        ctx.accounts.token_state.record_burn(burn_amount)?;
             
-        msg!("Burned {} tokens ({}% of transfer amount)", burn_amount, BURN_RATE);
+        msg!("Burned {} tokens ({}% of transfer amount)", burn_amount, pool_burn_rate);
     }
     
     msg!("Transferred {} tokens with {} tokens burned", transfer_amount, burn_amount);
@@ -312,3 +809,55 @@ pub fn transfer_with_limit(ctx: Context<TransferWithLimit>, amount: u64) -> Resu
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn milestones_queue_in_the_order_they_are_met() {
+        let mut token_state = TokenState::default();
+        token_state.record_self_sustaining(true);
+        token_state.record_entry_fee(ENTRY_FEES_MILESTONE_2).unwrap();
+
+        assert_eq!(
+            token_state.pending_mint_queue,
+            vec![
+                MILESTONE_SELF_SUSTAINING as u8,
+                MILESTONE_ENTRY_FEES_1 as u8,
+                MILESTONE_ENTRY_FEES_2 as u8,
+            ]
+        );
+        assert_eq!(token_state.pending_mint_milestone, Some(MILESTONE_SELF_SUSTAINING as u8));
+    }
+
+    #[test]
+    fn a_milestone_is_queued_once() {
+        let mut token_state = TokenState::default();
+        token_state.record_self_sustaining(true);
+        token_state.record_self_sustaining(false);
+        token_state.record_self_sustaining(true);
+        token_state.record_entry_fee(ENTRY_FEES_MILESTONE_1).unwrap();
+        token_state.record_entry_fee(1).unwrap();
+
+        assert_eq!(
+            token_state.pending_mint_queue,
+            vec![MILESTONE_SELF_SUSTAINING as u8, MILESTONE_ENTRY_FEES_1 as u8]
+        );
+    }
+
+    #[test]
+    fn next_mint_time_is_a_year_after_the_last_mint() {
+        let token_state = TokenState {
+            last_mint_timestamp: 1_000,
+            ..TokenState::default()
+        };
+        assert_eq!(token_state.next_mint_time().unwrap(), 1_000 + MIN_TIME_BETWEEN_MINTS);
+
+        let token_state = TokenState {
+            last_mint_timestamp: i64::MAX,
+            ..TokenState::default()
+        };
+        assert!(token_state.next_mint_time().is_err());
+    }
+}
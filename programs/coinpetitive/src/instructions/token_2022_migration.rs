@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{burn, Burn, Mint, Token, TokenAccount},
+    token_2022::Token2022,
+    token_interface::{
+        mint_to as mint_to_2022,
+        Mint as Mint2022,
+        MintTo as MintTo2022,
+        TokenAccount as TokenAccount2022,
+    },
+};
+
+use crate::instructions::token::TokenState;
+use crate::instructions::token_constants::CPT_TRANSFER_HOOK_PROGRAM_ID;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
+use crate::instructions::token_errors::TokenError;
+
+/*
+    migration path to a Token-2022 CPT mint
+    the new mint carries two extensions:
+    transfer hook       -> cpt_transfer_hook runs the pool burn and sell limit on every transfer
+    permanent delegate  -> the hook's burn authority, so accrued pool burns can be settled
+    holders swap 1:1, legacy tokens are burned, TokenState.current_supply stays the supply of record
+    pool burns settled by the hook are reported back through record_hook_burn
+*/
+
+#[derive(Accounts)]
+pub struct InitToken2022Mint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub legacy_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: The CPT transfer hook program
+    #[account(address = CPT_TRANSFER_HOOK_PROGRAM_ID)]
+    pub transfer_hook_program: UncheckedAccount<'info>,
+
+    /// CHECK: Burn authority PDA of the hook program, becomes the permanent delegate
+    #[account(
+        seeds = [b"burn_authority"],
+        bump,
+        seeds::program = transfer_hook_program.key(),
+    )]
+    pub hook_burn_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"mint_2022"],
+        bump,
+        mint::decimals = legacy_mint.decimals,
+        mint::authority = mint_2022,
+        mint::token_program = token_2022_program,
+        extensions::transfer_hook::authority = mint_2022,
+        extensions::transfer_hook::program_id = transfer_hook_program,
+        extensions::permanent_delegate::delegate = hook_burn_authority,
+    )]
+    pub mint_2022: Box<InterfaceAccount<'info, Mint2022>>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+// Only the hook's burn authority can sign, from inside settle_pool_burn
+#[derive(Accounts)]
+pub struct RecordHookBurn<'info> {
+    #[account(
+        seeds = [b"burn_authority"],
+        bump,
+        seeds::program = CPT_TRANSFER_HOOK_PROGRAM_ID,
+    )]
+    pub hook_burn_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Account<'info, TokenState>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateToToken2022<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub legacy_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = legacy_mint,
+        token::authority = owner,
+    )]
+    pub legacy_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_2022"],
+        bump,
+        mint::token_program = token_2022_program,
+    )]
+    pub mint_2022: Box<InterfaceAccount<'info, Mint2022>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_2022,
+        associated_token::authority = owner,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub token_2022_account: Box<InterfaceAccount<'info, TokenAccount2022>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_token_2022_mint(ctx: Context<InitToken2022Mint>) -> Result<()> {
+    ctx.accounts.token_state.token_2022_mint = ctx.accounts.mint_2022.key();
+
+    msg!("Token-2022 CPT mint created: {}", ctx.accounts.mint_2022.key());
+    msg!("Transfer hook program: {}", ctx.accounts.transfer_hook_program.key());
+    Ok(())
+}
+
+pub fn migrate_to_token_2022(ctx: Context<MigrateToToken2022>, amount: u64) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Migration)?;
+
+    require!(amount > 0, TokenError::InvalidMigrationAmount);
+    require!(
+        ctx.accounts.legacy_token_account.amount >= amount,
+        TokenError::InsufficientTokenBalance
+    );
+
+    // Retire the legacy tokens
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.legacy_mint.to_account_info(),
+                from: ctx.accounts.legacy_token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Issue the same amount on the Token-2022 mint
+    let seeds = &["mint_2022".as_bytes(), &[ctx.bumps.mint_2022]];
+    let signer = [&seeds[..]];
+    mint_to_2022(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_2022_program.to_account_info(),
+            MintTo2022 {
+                mint: ctx.accounts.mint_2022.to_account_info(),
+                to: ctx.accounts.token_2022_account.to_account_info(),
+                authority: ctx.accounts.mint_2022.to_account_info(),
+            },
+            &signer,
+        ),
+        amount,
+    )?;
+
+    // Total supply is unchanged, only its mint moved
+    let token_state = &mut ctx.accounts.token_state;
+    token_state.migrated_supply = token_state.migrated_supply
+        .checked_add(amount)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+
+    msg!("Migrated {} tokens to the Token-2022 mint", amount);
+    msg!("Total migrated supply: {}", token_state.migrated_supply);
+    Ok(())
+}
+
+pub fn record_hook_burn(ctx: Context<RecordHookBurn>, amount: u64) -> Result<()> {
+    let token_state = &mut ctx.accounts.token_state;
+    token_state.record_burn(amount)?;
+
+    msg!("Recorded {} tokens of settled pool burn", amount);
+    msg!("Current supply: {}", token_state.current_supply);
+    Ok(())
+}
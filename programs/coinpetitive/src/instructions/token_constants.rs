@@ -1,17 +1,43 @@
 use anchor_lang::prelude::*;
 
 // Constants based on tokenomics
+pub const CPT_DECIMALS: u8 = 9; // Every amount below assumes 9 decimals
 pub const INITIAL_SUPPLY: u64 = 21_000_000 * 1_000_000_000; // 21M tokens with 9 decimals
 pub const MAX_SUPPLY: u64 = 61_000_000 * 1_000_000_000; // 61M tokens with 9 decimals
 pub const FOUNDER_ALLOCATION: u64 = 1_100_000 * 1_000_000_000; // 1.1M tokens
 pub const DEV_ALLOCATION: u64 = 500_000 * 1_000_000_000; // 500K tokens
 pub const AFFILIATOR_ALLOCATION: u64 = 500_000 * 1_000_000_000; // 500K tokens
+pub const PUBLIC_ALLOCATION: u64 = INITIAL_SUPPLY - FOUNDER_ALLOCATION - DEV_ALLOCATION - AFFILIATOR_ALLOCATION; // 18.9M tokens for public use and liquidity
 pub const MINT_INCREMENT: u64 = 5_000_000 * 1_000_000_000; // 5M tokens for future mints
 pub const BURN_RATE: u8 = 1; // 1% burn rate on liquidity pool transactions
 pub const MIN_TIME_BETWEEN_MINTS: i64 = 31_536_000; // 60 * 60 * 24 * 365, one year in seconds
 
+// Minting trigger thresholds from the tokenomics table
+pub const CHALLENGES_MILESTONE_1: u64 = 5_000_000; // 5M challenges completed
+pub const CHALLENGES_MILESTONE_2: u64 = 10_000_000; // 10M challenges completed
+pub const ENTRY_FEES_MILESTONE_1: u64 = 50_000_000 * 1_000_000_000; // 50M tokens of entry fees
+pub const ENTRY_FEES_MILESTONE_2: u64 = 100_000_000 * 1_000_000_000; // 100M tokens of entry fees
+pub const MIN_COUNTED_PARTICIPANTS: usize = 3; // Distinct non-creator participants for a challenge to count
+pub const MIN_COUNTED_VOTERS: usize = 3; // Distinct non-creator voters for a challenge to count
+pub const WALLETS_MILESTONE_1: u64 = 250_000; // 250k unique holders
+pub const WALLETS_MILESTONE_2: u64 = 500_000; // 500k unique holders
+pub const WALLETS_MILESTONE_3: u64 = 1_000_000; // 1M unique holders
+pub const MIN_HOLDER_BALANCE: u64 = 100 * 1_000_000_000; // 100 tokens to count as a unique holder
+pub const MIN_HOLDER_AGE: i64 = 2_592_000; // 60 * 60 * 24 * 30, balance held 30 days before a holder counts
+pub const MAX_HOLDER_BATCH: usize = 10; // Holder records per confirm_holders call
+
+// Index of each trigger in TokenState.mint_conditions_met / mint_conditions_used
+pub const MILESTONE_CHALLENGES_1: usize = 0;
+pub const MILESTONE_CHALLENGES_2: usize = 1;
+pub const MILESTONE_ENTRY_FEES_1: usize = 2;
+pub const MILESTONE_ENTRY_FEES_2: usize = 3;
+pub const MILESTONE_WALLETS_1: usize = 4;
+pub const MILESTONE_WALLETS_2: usize = 5;
+pub const MILESTONE_WALLETS_3: usize = 6;
+pub const MILESTONE_SELF_SUSTAINING: usize = 7;
+
+// Token-2022 transfer hook program enforcing the pool burn and sell limit (programs/cpt-transfer-hook)
+pub const CPT_TRANSFER_HOOK_PROGRAM_ID: Pubkey = pubkey!("3VXWZaan98L91Li6j9fAhUwaGzjNjurVKfmmn5DToy4f");
+
 // Make sure this matches exactly what you use in the client
 pub const TOKEN_MINT_SEED: &[u8] = b"cpt_token_mint";
-
-// This is your default mint address - you won't need to validate against it anymore
-pub const CPT_TOKEN_MINT: &str = "mntjJeXswzxFCnCY1Zs2ekEzDvBVaVdyTVFXbBHfmo9";
@@ -37,5 +37,123 @@ pub enum TokenError {
     #[msg("Not authorized")]
     NotAuthorized,
     #[msg("Owner mismatch")]
-    OwnerMismatch
+    OwnerMismatch,
+    #[msg("Invalid attestor set")]
+    InvalidAttestorSet,
+    #[msg("Invalid attestation signature instruction")]
+    InvalidAttestation,
+    #[msg("Not enough attestor signatures")]
+    InsufficientAttestations,
+    #[msg("Genesis distribution has already been completed")]
+    GenesisAlreadyCompleted,
+    #[msg("Maximum supply of 61M tokens has not been reached")]
+    MaxSupplyNotReached,
+    #[msg("Pool is already registered")]
+    PoolAlreadyRegistered,
+    #[msg("Pool is not registered")]
+    PoolNotRegistered,
+    #[msg("Pool registry is full")]
+    PoolRegistryFull,
+    #[msg("Burn amount must be greater than zero")]
+    InvalidBurnAmount,
+    #[msg("Cost report does not match the open revenue period")]
+    InvalidRevenuePeriod,
+    #[msg("Revenue period has not ended yet")]
+    RevenuePeriodNotEnded,
+    #[msg("Invalid program config")]
+    InvalidConfig,
+    #[msg("Genesis distribution has not been completed")]
+    GenesisNotCompleted,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("Nothing to claim yet")]
+    NothingToClaim,
+    #[msg("Exceeds the category allocation")]
+    ExceedsAllocation,
+    #[msg("Wallet is not the registered beneficiary")]
+    InvalidBeneficiary,
+    #[msg("No pending beneficiary rotation for this category")]
+    NoPendingRotation,
+    #[msg("Beneficiary rotation delay has not passed")]
+    RotationNotReady,
+    #[msg("Invalid referral code")]
+    InvalidReferralCode,
+    #[msg("Cannot refer yourself")]
+    SelfReferral,
+    #[msg("Referrer has reached the referral bonus cap")]
+    ReferrerCapReached,
+    #[msg("Affiliator allocation is exhausted")]
+    AffiliatorAllocationExhausted,
+    #[msg("Invalid airdrop")]
+    InvalidAirdrop,
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Airdrop already claimed")]
+    AirdropAlreadyClaimed,
+    #[msg("Airdrop has expired")]
+    AirdropExpired,
+    #[msg("Airdrop has not expired yet")]
+    AirdropNotExpired,
+    #[msg("Airdrop already reclaimed")]
+    AirdropAlreadyReclaimed,
+    #[msg("Invalid random draw")]
+    InvalidRandomDraw,
+    #[msg("Random draw already revealed")]
+    DrawAlreadyRevealed,
+    #[msg("Random draw target slot has not passed")]
+    DrawNotReady,
+    #[msg("Seed does not match the committed hash")]
+    InvalidSeed,
+    #[msg("No slot hash at or after the target slot in SlotHashes")]
+    SlotHashUnavailable,
+    #[msg("Invalid activity snapshot")]
+    InvalidSnapshot,
+    #[msg("This instruction family is paused")]
+    InstructionPaused,
+    #[msg("This instruction family is not paused")]
+    NotPaused,
+    #[msg("No pending unpause for this instruction family")]
+    NoPendingUnpause,
+    #[msg("Unpause delay has not passed")]
+    UnpauseNotReady,
+    #[msg("Invalid multisig owners or threshold")]
+    InvalidMultisig,
+    #[msg("Signer is not a multisig owner")]
+    NotMultisigOwner,
+    #[msg("Invalid multisig proposal")]
+    InvalidProposal,
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    #[msg("Multisig owners changed since the proposal was made")]
+    ProposalOwnersChanged,
+    #[msg("Owner already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Not enough approvals to execute")]
+    InsufficientApprovals,
+    #[msg("Config update delay has not passed")]
+    ConfigUpdateNotReady,
+    #[msg("Release amount must be greater than zero")]
+    InvalidReleaseAmount,
+    #[msg("Holder still holds the minimum balance")]
+    HolderStillEligible,
+    #[msg("Vested allocations are paid through claim_vested")]
+    VestedCategory,
+    #[msg("Random draw reveal window has passed")]
+    DrawExpired,
+    #[msg("Random draw can still be revealed")]
+    DrawStillRevealable,
+    #[msg("Destination is not a consumer of this vault")]
+    InvalidReleaseDestination,
+    #[msg("Migration amount must be greater than zero")]
+    InvalidMigrationAmount,
+    #[msg("Withdrawal amount must be greater than zero")]
+    InvalidWithdrawalAmount,
+    #[msg("Revenue treasury can't cover the withdrawal")]
+    InsufficientRevenue,
+    #[msg("Vesting schedule already exists for this category")]
+    VestingScheduleExists,
+    #[msg("Claimant was not active in the snapshot's epoch")]
+    NotActiveInEpoch
 }
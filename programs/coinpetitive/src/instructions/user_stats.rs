@@ -0,0 +1,387 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::program_config::ProgramConfig;
+use crate::instructions::token_errors::TokenError;
+
+/*
+    per wallet activity behind the "airdrop to active challengers" milestone
+    pay_participation_fee, vote_for_submission and finalize_challenge count into the
+    wallet's stats for the current activity epoch
+    wallet's stats for the current activity epoch, and into an EpochActivity record
+    kept per (epoch, wallet) that never rolls over
+    snapshot_activity_epoch closes the epoch: later activity counts into the next one,
+    and the admin posts the merkle root built off-chain from the frozen counts
+    leaves use the airdrop leaf encoding, so the root goes straight into create_airdrop
+    an airdrop created from a snapshot only pays wallets whose EpochActivity for that
+    epoch shows activity, so a root listing anyone else can't be claimed from
+*/
+
+#[account]
+pub struct ActivityEpoch {
+    pub epoch: u64,
+    pub epoch_start: i64,
+    pub participations: u64, // Totals for the open epoch
+    pub votes: u64,
+    pub wins: u64,
+    pub active_wallets: u64,
+}
+
+impl ActivityEpoch {
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // epoch
+        8 + // epoch_start
+        8 + // participations
+        8 + // votes
+        8 + // wins
+        8; // active_wallets
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ActivityCounts {
+    pub participations: u32,
+    pub votes: u32,
+    pub wins: u32,
+}
+
+impl ActivityCounts {
+    pub const SPACE: usize = 4 + // participations
+        4 + // votes
+        4; // wins
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Activity {
+    Participation,
+    Vote,
+    Win,
+}
+
+#[account]
+pub struct UserStats {
+    pub wallet: Pubkey,
+    pub epoch: u64, // Epoch `current` counts into
+    pub current: ActivityCounts,
+    pub previous_epoch: u64, // Last epoch the wallet was active in before `epoch`
+    pub previous: ActivityCounts,
+    pub lifetime: ActivityCounts,
+}
+
+impl UserStats {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // wallet
+        8 + // epoch
+        ActivityCounts::SPACE + // current
+        8 + // previous_epoch
+        ActivityCounts::SPACE + // previous
+        ActivityCounts::SPACE; // lifetime
+
+    // Counts one activity into the open epoch, rolling stale counts into `previous`
+    pub fn record(
+        &mut self,
+        wallet: Pubkey,
+        activity: Activity,
+        activity_epoch: &mut ActivityEpoch,
+    ) -> Result<()> {
+        if self.wallet == Pubkey::default() {
+            self.wallet = wallet;
+        }
+        let first_this_epoch = self.epoch != activity_epoch.epoch || self.current == ActivityCounts::default();
+        if self.epoch != activity_epoch.epoch {
+            if self.current != ActivityCounts::default() {
+                self.previous_epoch = self.epoch;
+                self.previous = self.current;
+            }
+            self.epoch = activity_epoch.epoch;
+            self.current = ActivityCounts::default();
+        }
+        if first_this_epoch {
+            activity_epoch.active_wallets = activity_epoch.active_wallets
+                .checked_add(1)
+                .ok_or(TokenError::ArithmeticOverflow)?;
+        }
+
+        let (current, lifetime, total) = match activity {
+            Activity::Participation => (
+                &mut self.current.participations,
+                &mut self.lifetime.participations,
+                &mut activity_epoch.participations,
+            ),
+            Activity::Vote => (
+                &mut self.current.votes,
+                &mut self.lifetime.votes,
+                &mut activity_epoch.votes,
+            ),
+            Activity::Win => (
+                &mut self.current.wins,
+                &mut self.lifetime.wins,
+                &mut activity_epoch.wins,
+            ),
+        };
+        *current = current.checked_add(1).ok_or(TokenError::ArithmeticOverflow)?;
+        *lifetime = lifetime.checked_add(1).ok_or(TokenError::ArithmeticOverflow)?;
+        *total = total.checked_add(1).ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+// A wallet's counts for one epoch, what activity airdrop claims are checked against
+#[account]
+pub struct EpochActivity {
+    pub wallet: Pubkey,
+    pub epoch: u64,
+    pub counts: ActivityCounts,
+}
+
+impl EpochActivity {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // wallet
+        8 + // epoch
+        ActivityCounts::SPACE; // counts
+
+    // Called alongside UserStats::record with the open epoch's record
+    pub fn record(&mut self, wallet: Pubkey, epoch: u64, activity: Activity) -> Result<()> {
+        if self.wallet == Pubkey::default() {
+            self.wallet = wallet;
+            self.epoch = epoch;
+        }
+        let count = match activity {
+            Activity::Participation => &mut self.counts.participations,
+            Activity::Vote => &mut self.counts.votes,
+            Activity::Win => &mut self.counts.wins,
+        };
+        *count = count.checked_add(1).ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn was_active(&self) -> bool {
+        self.counts != ActivityCounts::default()
+    }
+}
+
+// Frozen totals of a closed epoch, and the root of its airdrop
+#[account]
+pub struct ActivitySnapshot {
+    pub epoch: u64,
+    pub epoch_start: i64,
+    pub epoch_end: i64,
+    pub participations: u64,
+    pub votes: u64,
+    pub wins: u64,
+    pub active_wallets: u64,
+    pub root: [u8; 32],
+    pub recipient_count: u32,
+}
+
+impl ActivitySnapshot {
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // epoch
+        8 + // epoch_start
+        8 + // epoch_end
+        8 + // participations
+        8 + // votes
+        8 + // wins
+        8 + // active_wallets
+        32 + // root
+        4; // recipient_count
+}
+
+#[derive(Accounts)]
+pub struct InitializeActivityEpoch<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ActivityEpoch::SPACE,
+        seeds = [b"activity_epoch"],
+        bump
+    )]
+    pub activity_epoch: Account<'info, ActivityEpoch>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SnapshotActivityEpoch<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"activity_epoch"],
+        bump,
+    )]
+    pub activity_epoch: Account<'info, ActivityEpoch>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ActivitySnapshot::SPACE,
+        seeds = [b"activity_snapshot", activity_epoch.epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub activity_snapshot: Account<'info, ActivitySnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_activity_epoch(ctx: Context<InitializeActivityEpoch>) -> Result<()> {
+    let activity_epoch = &mut ctx.accounts.activity_epoch;
+    activity_epoch.epoch = 0;
+    activity_epoch.epoch_start = Clock::get()?.unix_timestamp;
+    activity_epoch.participations = 0;
+    activity_epoch.votes = 0;
+    activity_epoch.wins = 0;
+    activity_epoch.active_wallets = 0;
+
+    msg!("Activity epoch 0 opened");
+    Ok(())
+}
+
+pub fn snapshot_activity_epoch(
+    ctx: Context<SnapshotActivityEpoch>,
+    root: [u8; 32],
+    recipient_count: u32,
+) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let activity_epoch = &mut ctx.accounts.activity_epoch;
+    require!(
+        recipient_count as u64 <= activity_epoch.active_wallets,
+        TokenError::InvalidSnapshot
+    );
+
+    let snapshot = &mut ctx.accounts.activity_snapshot;
+    snapshot.epoch = activity_epoch.epoch;
+    snapshot.epoch_start = activity_epoch.epoch_start;
+    snapshot.epoch_end = current_timestamp;
+    snapshot.participations = activity_epoch.participations;
+    snapshot.votes = activity_epoch.votes;
+    snapshot.wins = activity_epoch.wins;
+    snapshot.active_wallets = activity_epoch.active_wallets;
+    snapshot.root = root;
+    snapshot.recipient_count = recipient_count;
+
+    // Open the next epoch, freezing every wallet's counts for this one
+    activity_epoch.epoch = activity_epoch.epoch
+        .checked_add(1)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    activity_epoch.epoch_start = current_timestamp;
+    activity_epoch.participations = 0;
+    activity_epoch.votes = 0;
+    activity_epoch.wins = 0;
+    activity_epoch.active_wallets = 0;
+
+    msg!("Activity epoch {} snapshotted: {} active wallets", snapshot.epoch, snapshot.active_wallets);
+    msg!("Airdrop root posted for {} recipients", recipient_count);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch(epoch: u64) -> ActivityEpoch {
+        ActivityEpoch {
+            epoch,
+            epoch_start: 0,
+            participations: 0,
+            votes: 0,
+            wins: 0,
+            active_wallets: 0,
+        }
+    }
+
+    fn stats() -> UserStats {
+        UserStats {
+            wallet: Pubkey::default(),
+            epoch: 0,
+            current: ActivityCounts::default(),
+            previous_epoch: 0,
+            previous: ActivityCounts::default(),
+            lifetime: ActivityCounts::default(),
+        }
+    }
+
+    #[test]
+    fn a_wallet_counts_once_per_epoch_as_active() {
+        let wallet = Pubkey::new_unique();
+        let mut activity_epoch = epoch(0);
+        let mut stats = stats();
+        stats.record(wallet, Activity::Participation, &mut activity_epoch).unwrap();
+        stats.record(wallet, Activity::Vote, &mut activity_epoch).unwrap();
+
+        assert_eq!(stats.wallet, wallet);
+        assert_eq!(stats.current, ActivityCounts { participations: 1, votes: 1, wins: 0 });
+        assert_eq!(activity_epoch.active_wallets, 1);
+        assert_eq!(activity_epoch.participations, 1);
+        assert_eq!(activity_epoch.votes, 1);
+    }
+
+    #[test]
+    fn a_new_epoch_rolls_the_counts_into_previous() {
+        let wallet = Pubkey::new_unique();
+        let mut stats = stats();
+        stats.record(wallet, Activity::Win, &mut epoch(2)).unwrap();
+
+        let mut next = epoch(3);
+        stats.record(wallet, Activity::Vote, &mut next).unwrap();
+
+        assert_eq!(stats.epoch, 3);
+        assert_eq!(stats.current, ActivityCounts { participations: 0, votes: 1, wins: 0 });
+        assert_eq!(stats.previous_epoch, 2);
+        assert_eq!(stats.previous, ActivityCounts { participations: 0, votes: 0, wins: 1 });
+        assert_eq!(stats.lifetime, ActivityCounts { participations: 0, votes: 1, wins: 1 });
+        assert_eq!(next.active_wallets, 1);
+    }
+
+    #[test]
+    fn skipped_epochs_keep_the_last_active_one_as_previous() {
+        let wallet = Pubkey::new_unique();
+        let mut stats = stats();
+        stats.record(wallet, Activity::Participation, &mut epoch(1)).unwrap();
+        stats.record(wallet, Activity::Vote, &mut epoch(4)).unwrap();
+
+        assert_eq!(stats.previous_epoch, 1);
+        assert_eq!(stats.previous, ActivityCounts { participations: 1, votes: 0, wins: 0 });
+    }
+
+    #[test]
+    fn epoch_activity_keeps_its_own_epoch() {
+        let wallet = Pubkey::new_unique();
+        let mut record = EpochActivity { wallet: Pubkey::default(), epoch: 0, counts: ActivityCounts::default() };
+        assert!(!record.was_active());
+
+        record.record(wallet, 5, Activity::Participation).unwrap();
+        record.record(wallet, 5, Activity::Win).unwrap();
+
+        assert_eq!(record.wallet, wallet);
+        assert_eq!(record.epoch, 5);
+        assert_eq!(record.counts, ActivityCounts { participations: 1, votes: 0, wins: 1 });
+        assert!(record.was_active());
+    }
+
+    #[test]
+    fn the_first_activity_in_epoch_zero_counts_the_wallet() {
+        let wallet = Pubkey::new_unique();
+        let mut activity_epoch = epoch(0);
+        stats().record(wallet, Activity::Win, &mut activity_epoch).unwrap();
+
+        assert_eq!(activity_epoch.active_wallets, 1);
+        assert_eq!(activity_epoch.wins, 1);
+    }
+}
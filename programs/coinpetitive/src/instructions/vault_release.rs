@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::instructions::pool_registry::PoolRegistry;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
+use crate::instructions::token_errors::TokenError;
+
+/*
+    admin releases out of program-owned vaults into their program-defined consumers
+    public vault        -> a registered liquidity pool account (pool seeding)
+    rewards pool vault  -> the airdrop vault, paid out through merkle airdrop claims
+    liquidity vault     -> a registered liquidity pool account
+    every release is signed by the vault authority, needs the config admin
+    (the multisig signer once admin is handed to it) and is counted in the
+    vault release ledger
+*/
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReleaseVault {
+    Public,
+    RewardsPool,
+    Liquidity,
+}
+
+impl ReleaseVault {
+    pub fn vault_seed(&self) -> &'static [u8] {
+        match self {
+            ReleaseVault::Public => b"public_vault",
+            ReleaseVault::RewardsPool => b"rewards_pool_vault",
+            ReleaseVault::Liquidity => b"liquidity_vault",
+        }
+    }
+
+    // Releases never go to an arbitrary account, only to the vault's consumer
+    pub fn accepts_destination(
+        &self,
+        destination: &Pubkey,
+        pool_registry: &PoolRegistry,
+        program_id: &Pubkey,
+    ) -> bool {
+        match self {
+            ReleaseVault::Public | ReleaseVault::Liquidity => pool_registry.is_pool(destination),
+            ReleaseVault::RewardsPool => {
+                let (airdrop_vault, _) = Pubkey::find_program_address(&[b"airdrop_vault"], program_id);
+                *destination == airdrop_vault
+            }
+        }
+    }
+}
+
+#[account]
+pub struct VaultReleaseLedger {
+    pub released_from_public: u64,
+    pub released_from_rewards_pool: u64,
+    pub released_from_liquidity: u64,
+    pub release_count: u64,
+}
+
+impl VaultReleaseLedger {
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // released_from_public
+        8 + // released_from_rewards_pool
+        8 + // released_from_liquidity
+        8; // release_count
+
+    pub fn record_release(&mut self, vault: ReleaseVault, amount: u64) -> Result<()> {
+        let released = match vault {
+            ReleaseVault::Public => &mut self.released_from_public,
+            ReleaseVault::RewardsPool => &mut self.released_from_rewards_pool,
+            ReleaseVault::Liquidity => &mut self.released_from_liquidity,
+        };
+        *released = released
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        self.release_count = self.release_count
+            .checked_add(1)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+#[event]
+pub struct VaultReleased {
+    pub vault: ReleaseVault,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub released_by: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVaultReleaseLedger<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = VaultReleaseLedger::SPACE,
+        seeds = [b"vault_release_ledger"],
+        bump
+    )]
+    pub vault_release_ledger: Account<'info, VaultReleaseLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(vault: ReleaseVault)]
+pub struct ReleaseFromVault<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_release_ledger"],
+        bump,
+    )]
+    pub vault_release_ledger: Account<'info, VaultReleaseLedger>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: PDA that owns every program vault, never holds data
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Must be the program vault being released from
+    #[account(
+        mut,
+        seeds = [vault.vault_seed()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub source_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"pool_registry"],
+        bump,
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+
+    // Registered pool account or the airdrop vault, see ReleaseVault::accepts_destination
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn initialize_vault_release_ledger(ctx: Context<InitializeVaultReleaseLedger>) -> Result<()> {
+    let ledger = &mut ctx.accounts.vault_release_ledger;
+    ledger.released_from_public = 0;
+    ledger.released_from_rewards_pool = 0;
+    ledger.released_from_liquidity = 0;
+    ledger.release_count = 0;
+
+    msg!("Vault release ledger initialized");
+    Ok(())
+}
+
+pub fn release_from_vault(ctx: Context<ReleaseFromVault>, vault: ReleaseVault, amount: u64) -> Result<()> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Distribution)?;
+
+    require!(amount > 0, TokenError::InvalidReleaseAmount);
+    require!(
+        vault.accepts_destination(
+            &ctx.accounts.destination.key(),
+            &ctx.accounts.pool_registry,
+            ctx.program_id,
+        ),
+        TokenError::InvalidReleaseDestination
+    );
+    require!(
+        ctx.accounts.source_vault.amount >= amount,
+        TokenError::InsufficientTokenBalance
+    );
+
+    let seeds = &["vault_authority".as_bytes(), &[ctx.bumps.vault_authority]];
+    let signer = [&seeds[..]];
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.source_vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            &signer,
+        ),
+        amount,
+    )?;
+
+    ctx.accounts.vault_release_ledger.record_release(vault, amount)?;
+
+    emit!(VaultReleased {
+        vault,
+        amount,
+        destination: ctx.accounts.destination.key(),
+        released_by: ctx.accounts.admin.key(),
+    });
+    msg!("Released {} tokens from the {:?} vault to {}", amount, vault, ctx.accounts.destination.key());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_vaults_only_release_into_registered_pools() {
+        let pool = Pubkey::new_unique();
+        let registry = PoolRegistry { pools: vec![pool] };
+        let program_id = Pubkey::new_unique();
+
+        for vault in [ReleaseVault::Public, ReleaseVault::Liquidity] {
+            assert!(vault.accepts_destination(&pool, &registry, &program_id));
+            assert!(!vault.accepts_destination(&Pubkey::new_unique(), &registry, &program_id));
+        }
+    }
+
+    #[test]
+    fn the_rewards_pool_only_releases_into_the_airdrop_vault() {
+        let program_id = Pubkey::new_unique();
+        let (airdrop_vault, _) = Pubkey::find_program_address(&[b"airdrop_vault"], &program_id);
+        let pool = Pubkey::new_unique();
+        let registry = PoolRegistry { pools: vec![pool] };
+
+        assert!(ReleaseVault::RewardsPool.accepts_destination(&airdrop_vault, &registry, &program_id));
+        assert!(!ReleaseVault::RewardsPool.accepts_destination(&pool, &registry, &program_id));
+    }
+}
@@ -0,0 +1,317 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::instructions::beneficiary_registry::{AllocationCategory, BeneficiaryRegistry};
+use crate::instructions::distribution_ledger::DistributionLedger;
+use crate::instructions::program_config::{InstructionFamily, ProgramConfig};
+use crate::instructions::token::TokenState;
+use crate::instructions::token_errors::TokenError;
+
+/*
+    founder and dev allocations stay in their genesis vaults and unlock on a schedule
+    nothing unlocks before start + cliff
+    after that the allocation unlocks linearly until start + duration
+    a schedule is created once per category and can't be changed afterwards
+    each category has a minimum cliff and duration the admin can't go below
+    claims go to the category's wallet in the beneficiary registry
+*/
+
+pub const FOUNDER_MIN_CLIFF: i64 = 31_536_000; // 60 * 60 * 24 * 365, one year
+pub const FOUNDER_MIN_DURATION: i64 = 126_144_000; // four years
+pub const DEV_MIN_CLIFF: i64 = 15_768_000; // six months
+pub const DEV_MIN_DURATION: i64 = 63_072_000; // two years
+
+// Minimum (cliff, duration) for a category's schedule, None for categories that don't vest
+pub fn vesting_minimums(category: AllocationCategory) -> Option<(i64, i64)> {
+    match category {
+        AllocationCategory::Founder => Some((FOUNDER_MIN_CLIFF, FOUNDER_MIN_DURATION)),
+        AllocationCategory::Dev => Some((DEV_MIN_CLIFF, DEV_MIN_DURATION)),
+        AllocationCategory::Marketing => None,
+    }
+}
+
+pub fn validate_vesting_schedule(
+    category: AllocationCategory,
+    cliff_seconds: i64,
+    duration_seconds: i64,
+) -> Result<()> {
+    // Marketing is distributed, not vested
+    let (min_cliff, min_duration) = vesting_minimums(category)
+        .ok_or(TokenError::InvalidVestingSchedule)?;
+    require!(
+        cliff_seconds >= min_cliff && duration_seconds >= min_duration && cliff_seconds <= duration_seconds,
+        TokenError::InvalidVestingSchedule
+    );
+    Ok(())
+}
+
+#[account]
+pub struct VestingSchedule {
+    pub category: AllocationCategory,
+    pub total_amount: u64,
+    pub claimed: u64,
+    pub start_timestamp: i64,
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+}
+
+impl VestingSchedule {
+    pub const SPACE: usize = 8 + // discriminator
+        1 + // category
+        8 + // total_amount
+        8 + // claimed
+        8 + // start_timestamp
+        8 + // cliff_seconds
+        8; // duration_seconds
+
+    // Total unlocked at `timestamp`, claimed or not
+    pub fn vested_amount(&self, timestamp: i64) -> Result<u64> {
+        let elapsed = timestamp.saturating_sub(self.start_timestamp);
+        if elapsed < self.cliff_seconds {
+            return Ok(0);
+        }
+        if elapsed >= self.duration_seconds {
+            return Ok(self.total_amount);
+        }
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(TokenError::ArithmeticOverflow)?
+            / self.duration_seconds as u128;
+        Ok(vested as u64)
+    }
+
+    pub fn claimable_amount(&self, timestamp: i64) -> Result<u64> {
+        Ok(self.vested_amount(timestamp)?.saturating_sub(self.claimed))
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(category: AllocationCategory)]
+pub struct CreateVestingSchedule<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ TokenError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    // Vesting runs from the genesis mint, so the allocation must exist
+    #[account(
+        seeds = [b"token_state"],
+        bump,
+        constraint = token_state.genesis_completed @ TokenError::GenesisNotCompleted,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    // init_if_needed so an existing schedule is rejected with VestingScheduleExists
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = VestingSchedule::SPACE,
+        seeds = [b"vesting", category.seed()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(category: AllocationCategory)]
+pub struct ClaimVested<'info> {
+    #[account(
+        constraint = beneficiary.key() == beneficiary_registry.wallet_for(category) @ TokenError::InvalidBeneficiary,
+    )]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        seeds = [b"beneficiary_registry"],
+        bump,
+    )]
+    pub beneficiary_registry: Account<'info, BeneficiaryRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", category.seed()],
+        bump,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(
+        mut,
+        seeds = [b"distribution_ledger"],
+        bump,
+    )]
+    pub distribution_ledger: Account<'info, DistributionLedger>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: PDA that owns every program vault, never holds data
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [category.vault_seed()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = beneficiary,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn create_vesting_schedule(
+    ctx: Context<CreateVestingSchedule>,
+    category: AllocationCategory,
+    cliff_seconds: i64,
+    duration_seconds: i64,
+) -> Result<()> {
+    validate_vesting_schedule(category, cliff_seconds, duration_seconds)?;
+
+    // A created schedule always has a duration, a fresh account doesn't
+    let schedule = &mut ctx.accounts.vesting_schedule;
+    require!(schedule.duration_seconds == 0, TokenError::VestingScheduleExists);
+
+    schedule.category = category;
+    schedule.total_amount = category.allocation();
+    schedule.claimed = 0;
+    schedule.start_timestamp = ctx.accounts.token_state.genesis_timestamp;
+    schedule.cliff_seconds = cliff_seconds;
+    schedule.duration_seconds = duration_seconds;
+
+    msg!("Vesting schedule created for {:?}: {} tokens", category, schedule.total_amount);
+    msg!("Cliff {}s, duration {}s", cliff_seconds, duration_seconds);
+    Ok(())
+}
+
+// Returns the category's remaining allocation
+pub fn claim_vested(ctx: Context<ClaimVested>, category: AllocationCategory) -> Result<u64> {
+    ctx.accounts.program_config.require_active(InstructionFamily::Distribution)?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let amount = ctx.accounts.vesting_schedule.claimable_amount(current_timestamp)?;
+    require!(amount > 0, TokenError::NothingToClaim);
+
+    // Never more than the category allocation, whatever the schedule says
+    let claimed = ctx.accounts.vesting_schedule.claimed
+        .checked_add(amount)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    let remaining = ctx.accounts.distribution_ledger.record(category, amount)?;
+
+    let seeds = &["vault_authority".as_bytes(), &[ctx.bumps.vault_authority]];
+    let signer = [&seeds[..]];
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            &signer,
+        ),
+        amount,
+    )?;
+
+    ctx.accounts.vesting_schedule.claimed = claimed;
+
+    msg!("Claimed {} vested {:?} tokens", amount, category);
+    msg!("Total claimed: {} of {}", claimed, ctx.accounts.vesting_schedule.total_amount);
+    Ok(remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_000_000;
+
+    fn schedule() -> VestingSchedule {
+        VestingSchedule {
+            category: AllocationCategory::Founder,
+            total_amount: 1_200,
+            claimed: 0,
+            start_timestamp: START,
+            cliff_seconds: 100,
+            duration_seconds: 1_200,
+        }
+    }
+
+    #[test]
+    fn nothing_vests_before_the_cliff() {
+        let schedule = schedule();
+
+        assert_eq!(schedule.vested_amount(START - 50).unwrap(), 0);
+        assert_eq!(schedule.vested_amount(START + 99).unwrap(), 0);
+    }
+
+    #[test]
+    fn vesting_is_linear_from_the_start_once_the_cliff_passes() {
+        let schedule = schedule();
+
+        assert_eq!(schedule.vested_amount(START + 100).unwrap(), 100);
+        assert_eq!(schedule.vested_amount(START + 600).unwrap(), 600);
+    }
+
+    #[test]
+    fn everything_vests_at_the_end_of_the_duration() {
+        let schedule = schedule();
+
+        assert_eq!(schedule.vested_amount(START + 1_200).unwrap(), 1_200);
+        assert_eq!(schedule.vested_amount(i64::MAX).unwrap(), 1_200);
+    }
+
+    #[test]
+    fn claimable_excludes_what_was_already_claimed() {
+        let mut schedule = schedule();
+        schedule.claimed = 400;
+
+        assert_eq!(schedule.claimable_amount(START + 600).unwrap(), 200);
+        assert_eq!(schedule.claimable_amount(START + 300).unwrap(), 0);
+    }
+
+    #[test]
+    fn schedules_below_the_category_minimums_are_rejected() {
+        assert!(validate_vesting_schedule(AllocationCategory::Founder, FOUNDER_MIN_CLIFF, FOUNDER_MIN_DURATION).is_ok());
+        assert!(validate_vesting_schedule(AllocationCategory::Founder, FOUNDER_MIN_CLIFF - 1, FOUNDER_MIN_DURATION).is_err());
+        assert!(validate_vesting_schedule(AllocationCategory::Founder, FOUNDER_MIN_CLIFF, FOUNDER_MIN_DURATION - 1).is_err());
+        assert!(validate_vesting_schedule(AllocationCategory::Dev, DEV_MIN_CLIFF, DEV_MIN_DURATION).is_ok());
+        assert!(validate_vesting_schedule(AllocationCategory::Dev, 0, DEV_MIN_DURATION).is_err());
+        assert!(validate_vesting_schedule(AllocationCategory::Dev, DEV_MIN_DURATION + 1, DEV_MIN_DURATION).is_err());
+        assert!(validate_vesting_schedule(AllocationCategory::Marketing, DEV_MIN_CLIFF, DEV_MIN_DURATION).is_err());
+    }
+
+    #[test]
+    fn full_allocation_does_not_overflow() {
+        let mut schedule = schedule();
+        schedule.total_amount = u64::MAX;
+        schedule.duration_seconds = i64::MAX;
+
+        assert!(schedule.vested_amount(i64::MAX - 1).unwrap() < u64::MAX);
+    }
+}
@@ -1,10 +1,6 @@
 use anchor_lang::prelude::*;
 pub mod instructions;
 use instructions::*;
-use instructions::token::*;
-use instructions::token_constants::*;
-use instructions::token_errors::*;
-use instructions::challenge::*;
 
 
 
@@ -21,32 +17,310 @@ pub mod coinpetitive {
         instructions::token::initiate_token(ctx, metadata)
     }
     
-    pub fn mint_token(
-        ctx: Context<MintTokens>,
-        supply: u64,
+    pub fn initialize_program_config(
+        ctx: Context<InitializeProgramConfig>,
+        params: ProgramConfigParams
     ) -> Result<()> {
-        instructions::token::mint_tokens(ctx, supply)
+        instructions::program_config::initialize_program_config(ctx, params)
+    }
+
+    pub fn queue_config_update(
+        ctx: Context<QueueConfigUpdate>,
+        params: ProgramConfigParams
+    ) -> Result<()> {
+        instructions::program_config::queue_config_update(ctx, params)
+    }
+
+    pub fn cancel_config_update(ctx: Context<CancelConfigUpdate>) -> Result<()> {
+        instructions::program_config::cancel_config_update(ctx)
+    }
+
+    pub fn apply_config_update(ctx: Context<ApplyConfigUpdate>) -> Result<()> {
+        instructions::program_config::apply_config_update(ctx)
+    }
+
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8
+    ) -> Result<()> {
+        instructions::multisig::create_multisig(ctx, owners, threshold)
+    }
+
+    pub fn set_multisig_owners(
+        ctx: Context<SetMultisigOwners>,
+        owners: Vec<Pubkey>,
+        threshold: u8
+    ) -> Result<()> {
+        instructions::multisig::set_multisig_owners(ctx, owners, threshold)
+    }
+
+    pub fn propose_multisig_transaction(
+        ctx: Context<ProposeMultisigTransaction>,
+        instruction: ProposedInstruction,
+        expires_at: i64
+    ) -> Result<()> {
+        instructions::multisig::propose_multisig_transaction(ctx, instruction, expires_at)
+    }
+
+    pub fn approve_multisig_transaction(ctx: Context<ApproveMultisigTransaction>) -> Result<()> {
+        instructions::multisig::approve_multisig_transaction(ctx)
+    }
+
+    pub fn execute_multisig_transaction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteMultisigTransaction<'info>>
+    ) -> Result<()> {
+        instructions::multisig::execute_multisig_transaction(ctx)
+    }
+
+    pub fn pause_family(ctx: Context<GuardProgram>, family: InstructionFamily) -> Result<()> {
+        instructions::program_config::pause_family(ctx, family)
+    }
+
+    pub fn unpause_family(ctx: Context<GuardProgram>, family: InstructionFamily) -> Result<()> {
+        instructions::program_config::unpause_family(ctx, family)
+    }
+
+    pub fn execute_unpause(ctx: Context<GuardProgram>, family: InstructionFamily) -> Result<()> {
+        instructions::program_config::execute_unpause(ctx, family)
     }
     
-    pub fn transfer_founder(
-        ctx: Context<PartiesTr>,
+    pub fn mint_token(ctx: Context<MintTokens>) -> Result<()> {
+        instructions::token::mint_tokens(ctx)
+    }
+
+    pub fn execute_pending_mint(ctx: Context<MintTokens>) -> Result<i64> {
+        instructions::token::execute_pending_mint(ctx)
+    }
+    
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        params: UpdateTokenMetadataParams
+    ) -> Result<()> {
+        instructions::token::update_token_metadata(ctx, params)
+    }
+
+    pub fn retire_mint_authority(
+        ctx: Context<RetireMintAuthority>,
+        lock_metadata: bool
+    ) -> Result<()> {
+        instructions::token::retire_mint_authority(ctx, lock_metadata)
+    }
+
+    pub fn burn_tokens(
+        ctx: Context<BurnTokens>,
         amount: u64
     ) -> Result<()> {
-        founder_transfer(ctx, amount)
+        instructions::token::burn_tokens(ctx, amount)
     }
 
-    pub fn transfer_dev(
-        ctx: Context<PartiesTr>,
+    pub fn transfer_with_limit(
+        ctx: Context<TransferWithLimit>,
         amount: u64
     ) -> Result<()> {
-        dev_transfer(ctx, amount)
+        instructions::token::transfer_with_limit(ctx, amount)
     }
 
-    pub fn marketing_transfer(
-        ctx: Context<PartiesTr>,
+    pub fn initialize_pool_registry(ctx: Context<InitializePoolRegistry>) -> Result<()> {
+        instructions::pool_registry::initialize_pool_registry(ctx)
+    }
+
+    pub fn add_pool(ctx: Context<UpdatePoolRegistry>, pool: Pubkey) -> Result<()> {
+        instructions::pool_registry::add_pool(ctx, pool)
+    }
+
+    pub fn remove_pool(ctx: Context<UpdatePoolRegistry>, pool: Pubkey) -> Result<()> {
+        instructions::pool_registry::remove_pool(ctx, pool)
+    }
+
+    pub fn init_token_2022_mint(ctx: Context<InitToken2022Mint>) -> Result<()> {
+        instructions::token_2022_migration::init_token_2022_mint(ctx)
+    }
+
+    pub fn migrate_to_token_2022(
+        ctx: Context<MigrateToToken2022>,
+        amount: u64
+    ) -> Result<()> {
+        instructions::token_2022_migration::migrate_to_token_2022(ctx, amount)
+    }
+
+    pub fn record_hook_burn(
+        ctx: Context<RecordHookBurn>,
+        amount: u64
+    ) -> Result<()> {
+        instructions::token_2022_migration::record_hook_burn(ctx, amount)
+    }
+
+    pub fn register_holder(ctx: Context<RegisterHolder>) -> Result<()> {
+        instructions::holder_record::register_holder(ctx)
+    }
+
+    pub fn confirm_holders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfirmHolders<'info>>,
+        checked_at: i64
+    ) -> Result<()> {
+        instructions::holder_record::confirm_holders(ctx, checked_at)
+    }
+
+    pub fn close_holder_record(ctx: Context<CloseHolderRecord>) -> Result<()> {
+        instructions::holder_record::close_holder_record(ctx)
+    }
+
+    pub fn genesis_distribution(ctx: Context<GenesisDistribution>) -> Result<()> {
+        instructions::genesis::genesis_distribution(ctx)
+    }
+
+    pub fn initialize_emission_reserve(ctx: Context<InitializeEmissionReserve>) -> Result<()> {
+        instructions::emission::initialize_emission_reserve(ctx)
+    }
+
+    pub fn release_emission(
+        ctx: Context<ReleaseEmission>,
+        path: EmissionPath,
+        amount: u64
+    ) -> Result<()> {
+        instructions::emission::release_emission(ctx, path, amount)
+    }
+
+    pub fn initialize_vault_release_ledger(ctx: Context<InitializeVaultReleaseLedger>) -> Result<()> {
+        instructions::vault_release::initialize_vault_release_ledger(ctx)
+    }
+
+    pub fn release_from_vault(
+        ctx: Context<ReleaseFromVault>,
+        vault: ReleaseVault,
         amount: u64
     ) -> Result<()> {
-        do_marketing_transfer(ctx, amount)
+        instructions::vault_release::release_from_vault(ctx, vault, amount)
+    }
+
+    pub fn create_vesting_schedule(
+        ctx: Context<CreateVestingSchedule>,
+        category: AllocationCategory,
+        cliff_seconds: i64,
+        duration_seconds: i64
+    ) -> Result<()> {
+        instructions::vesting::create_vesting_schedule(ctx, category, cliff_seconds, duration_seconds)
+    }
+
+    pub fn claim_vested(
+        ctx: Context<ClaimVested>,
+        category: AllocationCategory
+    ) -> Result<u64> {
+        instructions::vesting::claim_vested(ctx, category)
+    }
+
+    pub fn create_airdrop(
+        ctx: Context<CreateAirdrop>,
+        airdrop_id: u64,
+        root: [u8; 32],
+        total_amount: u64,
+        num_recipients: u32,
+        expires_at: i64
+    ) -> Result<()> {
+        instructions::airdrop::create_airdrop(ctx, airdrop_id, root, total_amount, num_recipients, expires_at)
+    }
+
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>
+    ) -> Result<()> {
+        instructions::airdrop::claim_airdrop(ctx, index, amount, proof)
+    }
+
+    pub fn reclaim_airdrop(ctx: Context<ReclaimAirdrop>) -> Result<()> {
+        instructions::airdrop::reclaim_airdrop(ctx)
+    }
+
+    pub fn commit_random_draw(
+        ctx: Context<CommitRandomDraw>,
+        draw_id: u64,
+        seed_hash: [u8; 32],
+        eligibility_hash: [u8; 32],
+        eligible_count: u32,
+        winner_count: u32
+    ) -> Result<()> {
+        instructions::random_draw::commit_random_draw(ctx, draw_id, seed_hash, eligibility_hash, eligible_count, winner_count)
+    }
+
+    pub fn reveal_random_draw(ctx: Context<RevealRandomDraw>, seed: [u8; 32]) -> Result<()> {
+        instructions::random_draw::reveal_random_draw(ctx, seed)
+    }
+
+    pub fn expire_random_draw(ctx: Context<ExpireRandomDraw>) -> Result<()> {
+        instructions::random_draw::expire_random_draw(ctx)
+    }
+
+    pub fn initialize_activity_epoch(ctx: Context<InitializeActivityEpoch>) -> Result<()> {
+        instructions::user_stats::initialize_activity_epoch(ctx)
+    }
+
+    pub fn snapshot_activity_epoch(
+        ctx: Context<SnapshotActivityEpoch>,
+        root: [u8; 32],
+        recipient_count: u32
+    ) -> Result<()> {
+        instructions::user_stats::snapshot_activity_epoch(ctx, root, recipient_count)
+    }
+
+    pub fn create_referral_code(ctx: Context<CreateReferralCode>, code: String) -> Result<()> {
+        instructions::referral::create_referral_code(ctx, code)
+    }
+
+    pub fn claim_referral_bonus(ctx: Context<ClaimReferralBonus>) -> Result<()> {
+        instructions::referral::claim_referral_bonus(ctx)
+    }
+
+    pub fn initialize_distribution_ledger(ctx: Context<InitializeDistributionLedger>) -> Result<()> {
+        instructions::distribution_ledger::initialize_distribution_ledger(ctx)
+    }
+
+    pub fn remaining_allocation(
+        ctx: Context<ViewDistributionLedger>,
+        category: AllocationCategory
+    ) -> Result<u64> {
+        instructions::distribution_ledger::remaining_allocation(ctx, category)
+    }
+
+    pub fn initialize_beneficiary_registry(
+        ctx: Context<InitializeBeneficiaryRegistry>,
+        founder: Pubkey,
+        dev: Pubkey,
+        marketing: Pubkey
+    ) -> Result<()> {
+        instructions::beneficiary_registry::initialize_beneficiary_registry(ctx, founder, dev, marketing)
+    }
+
+    pub fn propose_beneficiary_rotation(
+        ctx: Context<GovernBeneficiaryRotation>,
+        category: AllocationCategory,
+        new_wallet: Pubkey
+    ) -> Result<()> {
+        instructions::beneficiary_registry::propose_beneficiary_rotation(ctx, category, new_wallet)
+    }
+
+    pub fn cancel_beneficiary_rotation(
+        ctx: Context<GovernBeneficiaryRotation>,
+        category: AllocationCategory
+    ) -> Result<()> {
+        instructions::beneficiary_registry::cancel_beneficiary_rotation(ctx, category)
+    }
+
+    pub fn execute_beneficiary_rotation(
+        ctx: Context<ExecuteBeneficiaryRotation>,
+        category: AllocationCategory
+    ) -> Result<()> {
+        instructions::beneficiary_registry::execute_beneficiary_rotation(ctx, category)
+    }
+
+    pub fn allocation_transfer(
+        ctx: Context<PartiesTr>,
+        category: AllocationCategory,
+        amount: u64
+    ) -> Result<u64> {
+        instructions::initialdistro::allocation_transfer(ctx, category, amount)
     }
 
     // Updated Challenge Functions
@@ -68,20 +342,20 @@ pub mod coinpetitive {
         )
     }
 
-    pub fn pay_participation_fee(
-        ctx: Context<PayParticipationFee>
+    pub fn pay_participation_fee<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PayParticipationFee<'info>>
     ) -> Result<()> {
         instructions::challenge::pay_participation_fee(ctx)
     }
 
-    pub fn vote_for_submission(
-        ctx: Context<VoteForSubmission>
+    pub fn vote_for_submission<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VoteForSubmission<'info>>
     ) -> Result<()> {
         instructions::challenge::vote_for_submission(ctx)
     }
 
-    pub fn finalize_challenge(
-        ctx: Context<FinalizeChallenge>,
+    pub fn finalize_challenge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeChallenge<'info>>,
         winner_pubkey: Pubkey,
         winning_votes: u64
     ) -> Result<()> {
@@ -92,15 +366,15 @@ pub mod coinpetitive {
         )
     }
     
-    pub fn submit_video(
-        ctx: Context<SubmitVideo>,
+    pub fn submit_video<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitVideo<'info>>,
         video_url: String
     ) -> Result<()> {
         instructions::challenge::submit_video(ctx, video_url)
     }
     
-    pub fn distribute_voting_treasury(
-        ctx: Context<DistributeVotingTreasury>,
+    pub fn distribute_voting_treasury<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeVotingTreasury<'info>>,
         voter: Pubkey,
         voter_index: u64
     ) -> Result<()> {
@@ -109,7 +383,9 @@ pub mod coinpetitive {
     
 
     
-    pub fn claim_creator_reward(ctx: Context<ClaimCreatorReward>) -> Result<()> {
+    pub fn claim_creator_reward<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimCreatorReward<'info>>
+    ) -> Result<()> {
         instructions::challenge::claim_creator_reward(ctx)
     }
 
@@ -117,8 +393,65 @@ pub mod coinpetitive {
         instructions::fee_tracking::initialize_fee_tracker(ctx)
     }
 
+    pub fn set_fee_tracker_authority(
+        ctx: Context<SetFeeTrackerAuthority>,
+        new_authority: Pubkey
+    ) -> Result<()> {
+        instructions::fee_tracking::set_fee_tracker_authority(ctx, new_authority)
+    }
+
     pub fn initialize_challenge_tracker(ctx: Context<InitializeChallengeTracker>) -> Result<()> {
         instructions::challenge_tracking::initialize_challenge_tracker(ctx)
     }
+
+    pub fn set_challenge_tracker_authority(
+        ctx: Context<SetChallengeTrackerAuthority>,
+        new_authority: Pubkey
+    ) -> Result<()> {
+        instructions::challenge_tracking::set_challenge_tracker_authority(ctx, new_authority)
+    }
+
+    pub fn initialize_attestor_set(
+        ctx: Context<InitializeAttestorSet>,
+        attestors: Vec<Pubkey>,
+        threshold: u8,
+        cluster: AttestationCluster
+    ) -> Result<()> {
+        instructions::attestor_set::initialize_attestor_set(ctx, attestors, threshold, cluster)
+    }
+
+    pub fn update_attestor_set(
+        ctx: Context<UpdateAttestorSet>,
+        attestors: Vec<Pubkey>,
+        threshold: u8
+    ) -> Result<()> {
+        instructions::attestor_set::update_attestor_set(ctx, attestors, threshold)
+    }
+
+    pub fn report_milestone(
+        ctx: Context<ReportMilestone>,
+        kind: MilestoneKind,
+        value: u64,
+        evidence_hash: [u8; 32]
+    ) -> Result<()> {
+        instructions::milestone_report::report_milestone(ctx, kind, value, evidence_hash)
+    }
+
+    pub fn initialize_revenue_ledger(ctx: Context<InitializeRevenueLedger>) -> Result<()> {
+        instructions::revenue_ledger::initialize_revenue_ledger(ctx)
+    }
+
+    pub fn report_operational_cost(
+        ctx: Context<ReportOperationalCost>,
+        period_index: u64,
+        operational_cost: u64,
+        evidence_hash: [u8; 32]
+    ) -> Result<()> {
+        instructions::revenue_ledger::report_operational_cost(ctx, period_index, operational_cost, evidence_hash)
+    }
+
+    pub fn withdraw_revenue(ctx: Context<WithdrawRevenue>, amount: u64) -> Result<()> {
+        instructions::revenue_ledger::withdraw_revenue(ctx, amount)
+    }
 }
 
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum HookError {
+    #[msg("Transfer hook called outside of a transfer")]
+    NotTransferring,
+    #[msg("Mint is not the Token-2022 CPT mint")]
+    InvalidMint,
+    #[msg("Rent payer can't fund a new sell tracker")]
+    RentPayerUnderfunded,
+    #[msg("Unsettled pool burn must stay in the source account")]
+    BurnOwed,
+    #[msg("No accrued burn to settle for this wallet")]
+    NoBurnToSettle,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow
+}
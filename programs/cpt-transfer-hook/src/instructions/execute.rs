@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions},
+    state::Account as SplTokenAccount,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use coinpetitive::instructions::pool_registry::PoolRegistry;
+use coinpetitive::instructions::sell_limit::is_program_vault;
+use coinpetitive::instructions::token::TokenState;
+use coinpetitive::instructions::program_config::ProgramConfig;
+
+use crate::errors::HookError;
+use crate::instructions::sell_tracker::{
+    load_or_create_sell_tracker, load_sell_tracker, save_sell_tracker, TrackerFunding,
+};
+use crate::state::HookConfig;
+
+// Account order is fixed by the transfer hook interface, see initialize.rs
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = mint.key() == token_state.token_2022_mint @ HookError::InvalidMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Owner or delegate of the source account, already verified by Token-2022
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Validation account holding the extra account metas
+    #[account(
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// CHECK: The coinpetitive program, needed to resolve its PDAs
+    #[account(address = coinpetitive::ID)]
+    pub coinpetitive_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"token_state"],
+        bump,
+        seeds::program = coinpetitive::ID,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    #[account(
+        seeds = [b"pool_registry"],
+        bump,
+        seeds::program = coinpetitive::ID,
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config"],
+        bump,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    /// CHECK: Sell tracker of the source owner, created by the hook on its first sell
+    #[account(
+        mut,
+        seeds = [b"sell_tracker", source_token.owner.as_ref()],
+        bump,
+    )]
+    pub sell_tracker: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        seeds::program = coinpetitive::ID,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Sell tracker of the destination owner, created by the hook on its first buy
+    #[account(
+        mut,
+        seeds = [b"sell_tracker", destination_token.owner.as_ref()],
+        bump,
+    )]
+    pub destination_sell_tracker: UncheckedAccount<'info>,
+
+    /// CHECK: System account funding new sell trackers, signs via seeds
+    #[account(
+        mut,
+        seeds = [b"rent_payer"],
+        bump,
+    )]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Reject direct calls: the source account must be mid-transfer inside Token-2022
+fn assert_is_transferring(source: &AccountInfo) -> Result<()> {
+    let data = source.try_borrow_data()?;
+    let account = StateWithExtensions::<SplTokenAccount>::unpack(&data)?;
+    let extension = account.get_extension::<TransferHookAccount>()?;
+    require!(bool::from(extension.transferring), HookError::NotTransferring);
+    Ok(())
+}
+
+pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+    assert_is_transferring(&ctx.accounts.source_token.to_account_info())?;
+
+    let source_key = ctx.accounts.source_token.key();
+    let destination_key = ctx.accounts.destination_token.key();
+    let registry = &ctx.accounts.pool_registry;
+    let is_sell = registry.is_pool(&destination_key);
+    let is_buy = registry.is_pool(&source_key);
+
+    let funding = TrackerFunding {
+        rent_payer: &ctx.accounts.rent_payer.to_account_info(),
+        rent_payer_bump: ctx.bumps.rent_payer,
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+    let source_tracker_info = ctx.accounts.sell_tracker.to_account_info();
+    // Trades are charged the pool burn on the trader's side, pool to pool hops aren't trades
+    let burn_amount = ctx.accounts.program_config.pool_burn(amount);
+
+    let source_tracker = if is_sell && !is_buy {
+        // Selling into a pool counts against the wallet's rolling 24-hour limit.
+        // Program vaults are exempt.
+        let seller = ctx.accounts.source_token.owner;
+        let mut tracker = load_or_create_sell_tracker(
+            &source_tracker_info,
+            ctx.bumps.sell_tracker,
+            &seller,
+            &funding,
+            ctx.program_id,
+        )?;
+        if !is_program_vault(&seller, &coinpetitive::ID) {
+            // Limit against the whole CPT supply, legacy and Token-2022 alike
+            let token_state = &ctx.accounts.token_state;
+            let total_supply = token_state.total_minted.saturating_sub(token_state.total_burned);
+            let daily_limit = ctx.accounts.program_config.daily_sell_limit(total_supply);
+            tracker.window.record_sell(Clock::get()?.unix_timestamp, amount, daily_limit)?;
+        }
+        tracker.accrue_burn(burn_amount)?;
+        save_sell_tracker(&source_tracker_info, &tracker)?;
+        Some(tracker)
+    } else {
+        if is_buy && !is_sell {
+            let buyer = ctx.accounts.destination_token.owner;
+            let destination_tracker_info = ctx.accounts.destination_sell_tracker.to_account_info();
+            let mut tracker = load_or_create_sell_tracker(
+                &destination_tracker_info,
+                ctx.bumps.destination_sell_tracker,
+                &buyer,
+                &funding,
+                ctx.program_id,
+            )?;
+            tracker.accrue_burn(burn_amount)?;
+            save_sell_tracker(&destination_tracker_info, &tracker)?;
+        }
+        load_sell_tracker(&source_tracker_info, ctx.program_id)?
+    };
+
+    if is_sell != is_buy && burn_amount > 0 {
+        ctx.accounts.hook_config.record_accrued(burn_amount)?;
+        msg!("Accrued {} tokens of pool burn", burn_amount);
+    }
+
+    // Whatever the wallet owes is withheld: it can't leave the source account until settled
+    if let Some(tracker) = source_tracker {
+        require!(
+            tracker.covers_burn(ctx.accounts.source_token.amount),
+            HookError::BurnOwed
+        );
+    }
+
+    Ok(())
+}
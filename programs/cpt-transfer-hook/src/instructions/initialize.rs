@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use coinpetitive::instructions::token::TokenState;
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta,
+    seeds::Seed,
+    state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+use crate::errors::HookError;
+use crate::state::HookConfig;
+
+/*
+    accounts Token-2022 passes to the hook on every transfer
+    0 source token account
+    1 mint
+    2 destination token account
+    3 owner / delegate
+    4 extra account meta list
+    extra accounts resolved from the list below:
+    5 coinpetitive program
+    6 coinpetitive token_state
+    7 coinpetitive pool_registry
+    8 hook_config
+    9 sell_tracker of the source account's owner
+    10 coinpetitive program_config
+    11 sell_tracker of the destination account's owner
+    12 rent_payer
+    13 system program
+*/
+const COINPETITIVE_PROGRAM_INDEX: u8 = 5;
+const SOURCE_ACCOUNT_INDEX: u8 = 0;
+const DESTINATION_ACCOUNT_INDEX: u8 = 2;
+// Owner field of an SPL token account
+const TOKEN_ACCOUNT_OWNER_OFFSET: u8 = 32;
+
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&coinpetitive::ID, false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            COINPETITIVE_PROGRAM_INDEX,
+            &[Seed::Literal { bytes: b"token_state".to_vec() }],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            COINPETITIVE_PROGRAM_INDEX,
+            &[Seed::Literal { bytes: b"pool_registry".to_vec() }],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: b"hook_config".to_vec() }],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"sell_tracker".to_vec() },
+                Seed::AccountData {
+                    account_index: SOURCE_ACCOUNT_INDEX,
+                    data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                    length: 32,
+                },
+            ],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            COINPETITIVE_PROGRAM_INDEX,
+            &[Seed::Literal { bytes: b"program_config".to_vec() }],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"sell_tracker".to_vec() },
+                Seed::AccountData {
+                    account_index: DESTINATION_ACCOUNT_INDEX,
+                    data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                    length: 32,
+                },
+            ],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: b"rent_payer".to_vec() }],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_pubkey(&anchor_lang::system_program::ID, false, false)?,
+    ])
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Validation account read by Token-2022, written in the handler
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    // Only the official Token-2022 CPT mint recorded by coinpetitive
+    #[account(
+        constraint = mint.key() == token_state.token_2022_mint @ HookError::InvalidMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"token_state"],
+        bump,
+        seeds::program = coinpetitive::ID,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = HookConfig::SPACE,
+        seeds = [b"hook_config"],
+        bump
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_extra_account_meta_list(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
+    let metas = extra_account_metas()?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+        &metas,
+    )?;
+
+    let hook_config = &mut ctx.accounts.hook_config;
+    hook_config.mint = ctx.accounts.mint.key();
+    hook_config.total_burn_accrued = 0;
+    hook_config.total_burn_settled = 0;
+
+    msg!("Transfer hook initialized for mint {}", ctx.accounts.mint.key());
+    Ok(())
+}
@@ -0,0 +1,8 @@
+pub mod initialize;
+pub use initialize::*;
+pub mod sell_tracker;
+pub use sell_tracker::*;
+pub mod execute;
+pub use execute::*;
+pub mod settle_burn;
+pub use settle_burn::*;
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer};
+use coinpetitive::instructions::sell_limit::SellWindow;
+
+use crate::errors::HookError;
+use crate::state::SellTracker;
+
+// Token-2022 strips signer privileges before calling the hook, so the hook pays for
+// trackers it creates mid-transfer out of the rent payer PDA, which anyone can top up
+// with a plain SOL transfer. Wallets can still create theirs up front here.
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct InitializeSellTracker<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = SellTracker::SPACE,
+        seeds = [b"sell_tracker", wallet.as_ref()],
+        bump
+    )]
+    pub sell_tracker: Account<'info, SellTracker>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_sell_tracker(ctx: Context<InitializeSellTracker>, wallet: Pubkey) -> Result<()> {
+    let sell_tracker = &mut ctx.accounts.sell_tracker;
+    sell_tracker.wallet = wallet;
+    sell_tracker.window = SellWindow::default();
+    sell_tracker.burn_owed = 0;
+
+    msg!("Sell tracker initialized for {}", wallet);
+    Ok(())
+}
+
+pub fn load_sell_tracker(tracker: &AccountInfo, program_id: &Pubkey) -> Result<Option<SellTracker>> {
+    if tracker.owner != program_id || tracker.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(SellTracker::try_deserialize(&mut &tracker.try_borrow_data()?[..])?))
+}
+
+// Accounts the hook needs to create a sell tracker on a wallet's first pool trade
+pub struct TrackerFunding<'a, 'info> {
+    pub rent_payer: &'a AccountInfo<'info>,
+    pub rent_payer_bump: u8,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+pub fn load_or_create_sell_tracker<'info>(
+    tracker: &AccountInfo<'info>,
+    tracker_bump: u8,
+    wallet: &Pubkey,
+    funding: &TrackerFunding<'_, 'info>,
+    program_id: &Pubkey,
+) -> Result<SellTracker> {
+    if let Some(existing) = load_sell_tracker(tracker, program_id)? {
+        return Ok(existing);
+    }
+
+    let rent_payer_seeds: &[&[u8]] = &[b"rent_payer", &[funding.rent_payer_bump]];
+    let tracker_seeds: &[&[u8]] = &[b"sell_tracker", wallet.as_ref(), &[tracker_bump]];
+    let required = Rent::get()?.minimum_balance(SellTracker::SPACE);
+    let top_up = required.saturating_sub(tracker.lamports());
+    require!(funding.rent_payer.lamports() >= top_up, HookError::RentPayerUnderfunded);
+
+    if tracker.lamports() == 0 {
+        create_account(
+            CpiContext::new_with_signer(
+                funding.system_program.clone(),
+                CreateAccount {
+                    from: funding.rent_payer.clone(),
+                    to: tracker.clone(),
+                },
+                &[rent_payer_seeds, tracker_seeds],
+            ),
+            required,
+            SellTracker::SPACE as u64,
+            program_id,
+        )?;
+    } else {
+        // Someone already sent lamports to the address, so create_account would fail
+        if top_up > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    funding.system_program.clone(),
+                    Transfer {
+                        from: funding.rent_payer.clone(),
+                        to: tracker.clone(),
+                    },
+                    &[rent_payer_seeds],
+                ),
+                top_up,
+            )?;
+        }
+        allocate(
+            CpiContext::new_with_signer(
+                funding.system_program.clone(),
+                Allocate { account_to_allocate: tracker.clone() },
+                &[tracker_seeds],
+            ),
+            SellTracker::SPACE as u64,
+        )?;
+        assign(
+            CpiContext::new_with_signer(
+                funding.system_program.clone(),
+                Assign { account_to_assign: tracker.clone() },
+                &[tracker_seeds],
+            ),
+            program_id,
+        )?;
+    }
+
+    msg!("Sell tracker created for {}", wallet);
+    Ok(SellTracker {
+        wallet: *wallet,
+        window: SellWindow::default(),
+        burn_owed: 0,
+    })
+}
+
+pub fn save_sell_tracker(tracker: &AccountInfo, sell_tracker: &SellTracker) -> Result<()> {
+    let mut data = tracker.try_borrow_mut_data()?;
+    sell_tracker.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
+use coinpetitive::cpi::accounts::RecordHookBurn;
+use coinpetitive::program::Coinpetitive;
+
+use crate::errors::HookError;
+use crate::state::{HookConfig, SellTracker};
+
+// Permissionless crank: burns the pool burn a trader owes out of one of their token
+// accounts using the burn authority PDA, which is the mint's permanent delegate,
+// and reports the burn to coinpetitive so TokenState.current_supply follows the mint.
+// Pool reserves are never touched.
+#[derive(Accounts)]
+pub struct SettlePoolBurn<'info> {
+    #[account(
+        mut,
+        seeds = [b"hook_config"],
+        bump,
+        has_one = mint,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sell_tracker", sell_tracker.wallet.as_ref()],
+        bump,
+    )]
+    pub sell_tracker: Account<'info, SellTracker>,
+
+    // Any CPT account of the trader, the hook keeps the owed burn in each of them
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = sell_tracker.wallet,
+    )]
+    pub trader_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Permanent delegate of the mint, signs via seeds
+    #[account(
+        seeds = [b"burn_authority"],
+        bump,
+    )]
+    pub burn_authority: UncheckedAccount<'info>,
+
+    /// CHECK: coinpetitive's token state, checked by record_hook_burn
+    #[account(mut)]
+    pub token_state: UncheckedAccount<'info>,
+
+    pub coinpetitive_program: Program<'info, Coinpetitive>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn settle_pool_burn(ctx: Context<SettlePoolBurn>) -> Result<()> {
+    let wallet = ctx.accounts.sell_tracker.wallet;
+    let owed = ctx.accounts.sell_tracker.burn_owed;
+    require!(owed > 0, HookError::NoBurnToSettle);
+
+    // Burn what the account can cover now, the rest stays owed
+    let amount = owed.min(ctx.accounts.trader_token_account.amount);
+    require!(amount > 0, HookError::NoBurnToSettle);
+
+    let seeds = &["burn_authority".as_bytes(), &[ctx.bumps.burn_authority]];
+    let signer = [&seeds[..]];
+    burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.trader_token_account.to_account_info(),
+                authority: ctx.accounts.burn_authority.to_account_info(),
+            },
+            &signer,
+        ),
+        amount,
+    )?;
+
+    coinpetitive::cpi::record_hook_burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.coinpetitive_program.to_account_info(),
+            RecordHookBurn {
+                hook_burn_authority: ctx.accounts.burn_authority.to_account_info(),
+                token_state: ctx.accounts.token_state.to_account_info(),
+            },
+            &signer,
+        ),
+        amount,
+    )?;
+
+    ctx.accounts.sell_tracker.settle_burn(amount)?;
+    ctx.accounts.hook_config.record_settled(amount)?;
+
+    msg!("Settled {} tokens of pool burn for {}", amount, wallet);
+    msg!("Total pool burn settled: {}", ctx.accounts.hook_config.total_burn_settled);
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+pub mod instructions;
+pub mod state;
+pub mod errors;
+use instructions::*;
+
+
+
+declare_id!("3VXWZaan98L91Li6j9fAhUwaGzjNjurVKfmmn5DToy4f");
+
+#[program]
+pub mod cpt_transfer_hook {
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>
+    ) -> Result<()> {
+        instructions::initialize::initialize_extra_account_meta_list(ctx)
+    }
+
+    pub fn initialize_sell_tracker(
+        ctx: Context<InitializeSellTracker>,
+        wallet: Pubkey
+    ) -> Result<()> {
+        instructions::sell_tracker::initialize_sell_tracker(ctx, wallet)
+    }
+
+    // Invoked by Token-2022 on every transfer of the CPT mint
+    #[interface(spl_transfer_hook_interface::execute)]
+    pub fn transfer_hook(
+        ctx: Context<TransferHook>,
+        amount: u64
+    ) -> Result<()> {
+        instructions::execute::transfer_hook(ctx, amount)
+    }
+
+    pub fn settle_pool_burn(ctx: Context<SettlePoolBurn>) -> Result<()> {
+        instructions::settle_burn::settle_pool_burn(ctx)
+    }
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use coinpetitive::instructions::sell_limit::SellWindow;
+
+use crate::errors::HookError;
+
+// Totals of the pool burn charged to traders. The hook can't move tokens itself,
+// so each trader's share is owed on their SellTracker until settle_pool_burn
+// burns it through the permanent delegate.
+#[account]
+pub struct HookConfig {
+    pub mint: Pubkey,
+    pub total_burn_accrued: u64,
+    pub total_burn_settled: u64,
+}
+
+impl HookConfig {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // mint
+        8 + // total_burn_accrued
+        8; // total_burn_settled
+
+    pub fn record_accrued(&mut self, amount: u64) -> Result<()> {
+        self.total_burn_accrued = self.total_burn_accrued
+            .checked_add(amount)
+            .ok_or(HookError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_settled(&mut self, amount: u64) -> Result<()> {
+        self.total_burn_settled = self.total_burn_settled
+            .checked_add(amount)
+            .ok_or(HookError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+// Hook-owned counterpart of coinpetitive's SellTracker, using the same rolling window.
+// Also carries the pool burn the wallet owes from its pool trades.
+#[account]
+#[derive(Default)]
+pub struct SellTracker {
+    pub wallet: Pubkey,
+    pub window: SellWindow,
+    pub burn_owed: u64, // Must stay in every source account of the wallet until settled
+}
+
+impl SellTracker {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // wallet
+        SellWindow::SPACE + // window
+        8; // burn_owed
+
+    pub fn accrue_burn(&mut self, amount: u64) -> Result<()> {
+        self.burn_owed = self.burn_owed
+            .checked_add(amount)
+            .ok_or(HookError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn settle_burn(&mut self, amount: u64) -> Result<()> {
+        self.burn_owed = self.burn_owed
+            .checked_sub(amount)
+            .ok_or(HookError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Balance left in a source account after a transfer must still cover the owed burn
+    pub fn covers_burn(&self, remaining_balance: u64) -> bool {
+        remaining_balance >= self.burn_owed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owed_burn_has_to_stay_in_the_source_account() {
+        let mut tracker = SellTracker::default();
+        tracker.accrue_burn(20).unwrap();
+
+        assert!(tracker.covers_burn(20));
+        assert!(!tracker.covers_burn(19));
+    }
+
+    #[test]
+    fn settling_releases_the_owed_burn() {
+        let mut tracker = SellTracker::default();
+        tracker.accrue_burn(20).unwrap();
+        tracker.settle_burn(15).unwrap();
+
+        assert_eq!(tracker.burn_owed, 5);
+        assert!(tracker.settle_burn(6).is_err());
+    }
+}
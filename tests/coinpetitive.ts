@@ -61,11 +61,11 @@ describe("coinpetitive", () => {
     //init_token(program, mint, metadataAddress, payer, token_metadata_program_id, metadata);
 
 
-    //mint_cpv(mint, payer, program, metadata, 21_000_000);
+    //mint_cpv(mint, program);
 
-    //transfer_to_founder(mint_addr, program, payer, metadata, senderTokenAccount, 500000);
+    //claim_vested(mint_addr, program, payer, { founder: {} });
 
-    //check_wallet_milestones(mint_addr, program, payer, metadata);
+    //crank_pending_mints(mint, program);
 
 
     monitor_challenge_completions(program);
@@ -313,166 +313,76 @@ function init_token(program , mint , metadataAddress , payer , token_metadata_pr
     });
 }
 
-function mint_cpv(mint, payer, program, metadata, supply){
-  it("mint tokens", async () => {
-        console.log("============================");
-        console.log("Minting to token address:", mint.toString());
-        console.log("Supply amount:", supply);
-        console.log("============================");
-        
-        const destination = await anchor.utils.token.associatedAddress({
-          mint: mint,
-          owner: payer,
-        });
-  
-        let initialBalance = 0;
-        try {
-          const balance = await program.provider.connection.getTokenAccountBalance(destination);
-          initialBalance = balance.value.uiAmount;
-          console.log(`  Initial balance: ${initialBalance}`);
-        } catch (e) {
-          console.log("  No initial balance found");
-        }
-    
-        const context = {
-          mint,
-          destination,
-          payer,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-          systemProgram: anchor.web3.SystemProgram.programId,
-          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-        };
-    
-        const amountToMint = supply;
-        const amount = new anchor.BN(amountToMint * Math.pow(10, metadata.decimals));
-        
-        console.log(`  About to mint ${amountToMint} tokens to ${payer.toString()}`);
-        
-        const txHash = await program.methods
-          .mintToken(amount)
-          .accounts(context)
-          .rpc();
-        await program.provider.connection.confirmTransaction(txHash);
-        console.log(`  https://explorer.solana.com/tx/${txHash}?cluster=devnet`);
-    
-        await new Promise(resolve => setTimeout(resolve, 2000));
-    
-        const postBalance = (
-          await program.provider.connection.getTokenAccountBalance(destination)
-        ).value.uiAmount;
-        
-        console.log(`  Final balance: ${postBalance}`);
-        assert.equal(
-          postBalance, 
-          initialBalance + amountToMint, 
-          "Balance should be increased by exactly the minted amount"
-        );
-      });
-}
+// Mints the next queued milestone into the emission reserve, MINT_INCREMENT every time
+function mint_cpv(mint, program){
+  it("mints the next queued milestone into the emission reserve", async () => {
+    const pda = (seed: string) =>
+      anchor.web3.PublicKey.findProgramAddressSync([Buffer.from(seed)], program.programId)[0];
+    const tokenState = pda("token_state");
+    const emissionReserve = pda("emission_reserve");
+
+    const before = await program.account.tokenState.fetch(tokenState);
+    assert(before.pendingMintQueue.length > 0, "No milestone is queued for minting");
+    const initialBalance = new BN(
+      (await program.provider.connection.getTokenAccountBalance(emissionReserve)).value.amount
+    );
 
-function transfer_to_founder(mint_addr , program , payer , metadata , senderTokenAccount , tk){
-  it("transfers tokens to the founder's ATA", async () => {
-    
-    const founderWallet = new anchor.web3.PublicKey("FuFzoMF5xTwZego84fRoscnart4dPYNkpHho2UBe7NDt");
-    const recipientAta = getAssociatedTokenAddressSync(mint_addr, founderWallet);
-    
-      try {
-        const accountInfo = await program.provider.connection.getAccountInfo(recipientAta);
-        if (!accountInfo) {
-          console.log("ATA does not exist. Creating ATA...");
-          const createAtaIx = createAssociatedTokenAccountInstruction(
-            payer,          // payer of the transaction
-            recipientAta,   // the ATA to be created
-            founderWallet,  // owner of the ATA
-            mint_addr       // token mint
-          );
-          const createAtaTx = new anchor.web3.Transaction().add(createAtaIx);
-          await program.provider.sendAndConfirm(createAtaTx);
-          console.log("Created founder's associated token account");
-        } else {
-          console.log("Found existing ATA for founder.");
-        }
-      } catch (e) {
-        console.error("Error checking ATA; attempting to create:", e);
-        const createAtaIx = createAssociatedTokenAccountInstruction(
-          payer,
-          recipientAta,
-          founderWallet,
-          mint_addr
-        );
-        const createAtaTx = new anchor.web3.Transaction().add(createAtaIx);
-        await program.provider.sendAndConfirm(createAtaTx);
-      }
-    
-  
+    const txHash = await program.methods
+      .mintToken()
+      .accounts({
+        mint,
+        vaultAuthority: pda("vault_authority"),
+        emissionReserve,
+        emissionLedger: pda("emission_ledger"),
+        tokenState,
+        programConfig: pda("program_config"),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    console.log(`  https://explorer.solana.com/tx/${txHash}?cluster=devnet`);
 
-      const amountToMint = tk;
-      const amount = new anchor.BN(amountToMint * Math.pow(10, metadata.decimals));
-      
-      const txSignature = await program.methods
-        .transferFounder(amount)
-        .accounts({
-          from: senderTokenAccount,
-          to: recipientAta,
-          authority: payer,
-          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
-        })
-        .rpc();
-      console.log(`Transfer successful: https://explorer.solana.com/tx/${txSignature}?cluster=devnet`);
-    });
+    const MINT_INCREMENT = new BN(5_000_000).mul(new BN(10).pow(new BN(9)));
+    const postBalance = new BN(
+      (await program.provider.connection.getTokenAccountBalance(emissionReserve)).value.amount
+    );
+    assert.equal(
+      postBalance.sub(initialBalance).toString(),
+      MINT_INCREMENT.toString(),
+      "Emission reserve should grow by exactly one mint increment"
+    );
+
+    const after = await program.account.tokenState.fetch(tokenState);
+    assert.equal(after.pendingMintQueue.length, before.pendingMintQueue.length - 1);
+  });
 }
 
-function transfer_to_dev(mint_addr , program , payer , metadata , senderTokenAccount , tk){
-  it("transfers tokens to the dev's ATA", async () => {
-    
-      const founderWallet = new anchor.web3.PublicKey("DhCu49epRCawP9Yp2ZoatzSvfmTewi2x73xEM6Vb2kh2");
-      const recipientAta = getAssociatedTokenAddressSync(mint_addr, founderWallet);
-      
-        try {
-          const accountInfo = await program.provider.connection.getAccountInfo(recipientAta);
-          if (!accountInfo) {
-            console.log("ATA does not exist. Creating ATA...");
-            const createAtaIx = createAssociatedTokenAccountInstruction(
-              payer,          
-              recipientAta,   // the ATA to be created
-              founderWallet,  // owner of the ATA
-              mint_addr       // token mint
-            );
-            const createAtaTx = new anchor.web3.Transaction().add(createAtaIx);
-            await program.provider.sendAndConfirm(createAtaTx);
-            console.log("Created founder's associated token account");
-          } else {
-            console.log("Found existing ATA for dev team.");
-          }
-        } catch (e) {
-          console.error("Error checking ATA; attempting to create:", e);
-          const createAtaIx = createAssociatedTokenAccountInstruction(
-            payer,
-            recipientAta,
-            founderWallet,
-            mint_addr
-          );
-          const createAtaTx = new anchor.web3.Transaction().add(createAtaIx);
-          await program.provider.sendAndConfirm(createAtaTx);
-        }
-      
-    
-  
-        const amountToMint = tk; //500k
-        const amount = new anchor.BN(amountToMint * Math.pow(10, metadata.decimals));
-        
-        const txSignature = await program.methods
-          .transferDev(amount)
-          .accounts({
-            from: senderTokenAccount,
-            to: recipientAta,
-            authority: payer,
-            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
-          })
-          .rpc();
-        console.log(`Transfer successful: https://explorer.solana.com/tx/${txSignature}?cluster=devnet`);
-      });
+// category is { founder: {} } or { dev: {} }, payer must be the schedule's beneficiary
+function claim_vested(mint_addr, program, payer, category){
+  it("claims the vested part of a team allocation", async () => {
+    const destination = getAssociatedTokenAddressSync(mint_addr, payer);
+
+    const accountInfo = await program.provider.connection.getAccountInfo(destination);
+    if (!accountInfo) {
+      const createAtaIx = createAssociatedTokenAccountInstruction(
+        payer,
+        destination,
+        payer,
+        mint_addr
+      );
+      await program.provider.sendAndConfirm(new anchor.web3.Transaction().add(createAtaIx));
+      console.log("Created beneficiary's associated token account");
+    }
+
+    const txSignature = await program.methods
+      .claimVested(category)
+      .accounts({
+        beneficiary: payer,
+        destination: destination,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
+      })
+      .rpc();
+    console.log(`Claim successful: https://explorer.solana.com/tx/${txSignature}?cluster=devnet`);
+  });
 }
 
 function transfer_to_marketing(mint_addr, program, payer, metadata, senderTokenAccount, tk) {
@@ -516,11 +426,10 @@ function transfer_to_marketing(mint_addr, program, payer, metadata, senderTokenA
     const amount = new anchor.BN(amountToMint * Math.pow(10, metadata.decimals));
     
     const txSignature = await program.methods
-      .marketingTransfer(amount)
+      .allocationTransfer({ marketing: {} }, amount)
       .accounts({
-        from: senderTokenAccount,
+        admin: payer,
         to: recipientAta,
-        authority: payer,
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID
       })
       .preInstructions([modifyComputeUnits])
@@ -529,610 +438,54 @@ function transfer_to_marketing(mint_addr, program, payer, metadata, senderTokenA
   });
 }
 
-
-
-// done
-function check_wallet_milestones(mint_addr, program, payer, metadata) {
-  it("continuously checks wallet milestones every 5 seconds", async function() {
-    // Set a longer timeout for this test (default is 2000ms)
-    this.timeout(3600000); // 1 hour
-    
-    console.log("============================");
-    console.log("Starting continuous wallet milestone monitoring...");
-    console.log("Press Ctrl+C to stop the test");
-    console.log("============================");
-    
-    // Define wallet milestones (in production these would be 250k, 500k, 1M)
-    // For testing, we use much smaller numbers
-    const WALLET_MILESTONES = [
-      { threshold: 250000, index: 4, name: "Test 250k wallets" },
-      { threshold: 500000, index: 5, name: "Test 500k wallets" },
-      { threshold: 1000000, index: 6, name: "Test 1M wallets" }
-    ];
-    
-    // Get token state address
-    const [tokenStateAddress] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("token_state")],
-      program.programId
-    );
-    
-    // Keep track of milestones we have locally minted for
-    const locallyMintedMilestones = new Set();
-    
-    // Run continuous monitoring
-    const checkInterval = 5000; // 5 seconds
-    let checkCount = 0;
-    
-    // Use a promise that we deliberately don't resolve
-    // This keeps the test running until manually terminated
-    return new Promise(async () => {
-      while (true) {
-        try {
-          checkCount++;
-          console.log(`\n[Check #${checkCount}] ${new Date().toLocaleTimeString()}`);
-          
-          // Find all token accounts for this mint
-          console.log("Scanning for wallet holders...");
-          const connection = program.provider.connection;
-          const tokenAccounts = await connection.getProgramAccounts(
-            TOKEN_PROGRAM_ID,
-            {
-              filters: [
-                { dataSize: 165 }, // Token account size
-                { memcmp: { offset: 0, bytes: mint_addr.toBase58() } }, // Filter by mint
-              ],
-            }
-          );
-          
-          // Count unique wallet addresses with non-zero balance
-          const uniqueWallets = new Set();
-          for (const account of tokenAccounts) {
-            const data = account.account.data;
-            const owner = new PublicKey(data.slice(32, 64));
-            const amount = Number(data.readBigUInt64LE(64));
-            
-            if (amount > 0) {
-              uniqueWallets.add(owner.toBase58());
-            }
-          }
-          
-          const walletCount = uniqueWallets.size;
-          console.log(`Found ${walletCount} unique wallets with non-zero balances`);
-          
-          // Always fetch fresh token state on each iteration
-          const tokenState = await program.account.tokenState.fetch(tokenStateAddress);
-          const mintConditionsUsed = tokenState.mintConditionsUsed;
-          
-          // Debug logging
-          console.log("Current mint conditions used:", mintConditionsUsed);
-          
-          // Check each milestone
-          let mintingExecuted = false;
-          for (const milestone of WALLET_MILESTONES) {
-            if (walletCount >= milestone.threshold) {
-              console.log(`🎉 Milestone reached: ${milestone.name} (${walletCount} wallets)`);
-              
-              // Triple check to avoid duplicate minting:
-              // 1. Check on-chain state
-              // 2. Check local tracking
-              const onChainUsed = mintConditionsUsed[milestone.index];
-              const locallyUsed = locallyMintedMilestones.has(milestone.index);
-              
-              if (onChainUsed || locallyUsed) {
-                console.log(`Milestone ${milestone.name} already used for minting (on-chain: ${onChainUsed}, local: ${locallyUsed})`);
-                continue;
-              }
-              
-              // Check time restriction
-              const currentTimestamp = Math.floor(Date.now() / 1000);
-              const lastMintTimestamp = tokenState.lastMintTimestamp.toNumber();
-              const MIN_TIME_BETWEEN_MINTS = 365 * 24 * 60 * 60; // 1 year in seconds
-              
-              // For testing, we can bypass the time restriction
-              // In production, uncomment this:
-              /*
-              if (currentTimestamp - lastMintTimestamp < MIN_TIME_BETWEEN_MINTS) {
-                const daysRemaining = Math.ceil((MIN_TIME_BETWEEN_MINTS - (currentTimestamp - lastMintTimestamp)) / (24 * 60 * 60));
-                console.log(`Time restriction applies: ${daysRemaining} days remaining`);
-                continue;
-              }
-              */
-              
-              console.log(`💰 Minting 5M tokens for milestone: ${milestone.name}`);
-              
-              try {
-                // Set up destination for minted tokens
-                const destination = await anchor.utils.token.associatedAddress({
-                  mint: mint_addr,
-                  owner: payer,
-                });
-                
-                // Mint 5M tokens (MINT_INCREMENT)
-                const mintAmount = new anchor.BN(5_000_000 * Math.pow(10, metadata.decimals));
-                
-                const txHash = await program.methods
-                  .mintToken(mintAmount)
-                  .accounts({
-                    mint: mint_addr,
-                    destination,
-                    tokenState: tokenStateAddress,
-                    payer,
-                    rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-                    systemProgram: anchor.web3.SystemProgram.programId,
-                    tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-                    associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-                  })
-                  .rpc();
-                
-                // Wait for confirmation
-                await program.provider.connection.confirmTransaction(txHash);
-                  
-                console.log(`✅ Milestone mint successful! TX: https://explorer.solana.com/tx/${txHash}?cluster=devnet`);
-                
-                // Mark as locally minted to prevent duplicate mints
-                locallyMintedMilestones.add(milestone.index);
-                
-                // Flag that minting was executed
-                mintingExecuted = true;
-                
-                // Only mint for one milestone at a time
-                break;
-              } catch (error) {
-                console.error(`❌ Error minting for milestone:`, error);
-              }
-            }
-          }
-          
-          // Extra safety: If we did a mint, wait a few seconds and refresh state
-          if (mintingExecuted) {
-            console.log("Waiting for state to update after minting...");
-            await new Promise(resolve => setTimeout(resolve, 5000));
-            
-            // Verify the milestone was correctly marked as used on-chain
-            const updatedTokenState = await program.account.tokenState.fetch(tokenStateAddress);
-            console.log("Updated mint conditions used after minting:", updatedTokenState.mintConditionsUsed);
-          }
-          
-          console.log(`Monitoring active... Next check in 5 seconds`);
-          
-          // Wait 5 seconds before checking again
-          await new Promise(resolve => setTimeout(resolve, checkInterval));
-        } catch (error) {
-          console.error("Error during milestone check:", error);
-          // Continue the loop even after errors
-          await new Promise(resolve => setTimeout(resolve, checkInterval));
-        }
-      }
-    });
-  });
-}
-
-
-
-
-
-
-
-
-
-
-function check_entry_fee_milestones(mint_addr, program, payer, metadata) {
-  it("continuously checks entry fee milestones every 5 seconds", async function() {
-    // Set a longer timeout for this test (default is 2000ms)
+// Milestones are tracked on-chain and queued as they are met, so the only off-chain
+// job left is cranking execute_pending_mint once the mint cooldown has passed
+function crank_pending_mints(mint, program) {
+  it("cranks queued milestone mints into the emission reserve", async function() {
     this.timeout(3600000); // 1 hour
-    
-    console.log("============================");
-    console.log("Starting continuous entry fee milestone monitoring...");
-    console.log("Press Ctrl+C to stop the test");
-    console.log("============================");
-    
-    // Define entry fee milestones based on tokenomics
-    const ENTRY_FEE_MILESTONES = [
-      { threshold: 50_000_000, index: 2, name: "50M total entry fees" },
-      { threshold: 100_000_000, index: 3, name: "100M total entry fees" }
-    ];
-    
-    // For testing, you might want to use smaller thresholds:
-    // const ENTRY_FEE_MILESTONES = [
-    //   { threshold: 5, index: 2, name: "Test 5 entry fees" },
-    //   { threshold: 10, index: 3, name: "Test 10 entry fees" }
-    // ];
-    
-    // Get token state address
-    const [tokenStateAddress] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("token_state")],
-      program.programId
-    );
-    
-    // Keep track of milestones we have locally minted for
-    const locallyMintedMilestones = new Set();
-    
-    // Run continuous monitoring
-    const checkInterval = 5000; // 5 seconds
-    let checkCount = 0;
-    
-    // Use a promise that we deliberately don't resolve
-    // This keeps the test running until manually terminated
-    return new Promise(async () => {
-      while (true) {
-        try {
-          checkCount++;
-          console.log(`\n[Check #${checkCount}] ${new Date().toLocaleTimeString()}`);
-          
-          // Fetch token state to get total entry fees
-          const tokenState = await program.account.tokenState.fetch(tokenStateAddress);
-          const totalEntryFees = tokenState.totalEntryFees.toNumber();
-          const mintConditionsUsed = tokenState.mintConditionsUsed;
-          
-          console.log(`Current total entry fees: ${totalEntryFees}`);
-          console.log("Current mint conditions used:", mintConditionsUsed);
-          
-          // Check each milestone
-          let mintingExecuted = false;
-          for (const milestone of ENTRY_FEE_MILESTONES) {
-            if (totalEntryFees >= milestone.threshold) {
-              console.log(`🎉 Milestone reached: ${milestone.name} (${totalEntryFees} fees paid)`);
-              
-              // Triple check to avoid duplicate minting:
-              // 1. Check on-chain state
-              // 2. Check local tracking
-              const onChainUsed = mintConditionsUsed[milestone.index];
-              const locallyUsed = locallyMintedMilestones.has(milestone.index);
-              
-              if (onChainUsed || locallyUsed) {
-                console.log(`Milestone ${milestone.name} already used for minting (on-chain: ${onChainUsed}, local: ${locallyUsed})`);
-                continue;
-              }
-              
-              // Check time restriction
-              const currentTimestamp = Math.floor(Date.now() / 1000);
-              const lastMintTimestamp = tokenState.lastMintTimestamp.toNumber();
-              const MIN_TIME_BETWEEN_MINTS = 365 * 24 * 60 * 60; // 1 year in seconds
-              
-              // For testing, we can bypass the time restriction
-              // In production, uncomment this:
-              /*
-              if (currentTimestamp - lastMintTimestamp < MIN_TIME_BETWEEN_MINTS) {
-                const daysRemaining = Math.ceil((MIN_TIME_BETWEEN_MINTS - (currentTimestamp - lastMintTimestamp)) / (24 * 60 * 60));
-                console.log(`Time restriction applies: ${daysRemaining} days remaining`);
-                continue;
-              }
-              */
-              
-              console.log(`💰 Minting 5M tokens for milestone: ${milestone.name}`);
-              
-              try {
-                // Set up destination for minted tokens
-                const destination = await anchor.utils.token.associatedAddress({
-                  mint: mint_addr,
-                  owner: payer,
-                });
-                
-                // Mint 5M tokens (MINT_INCREMENT)
-                const mintAmount = new anchor.BN(5_000_000 * Math.pow(10, metadata.decimals));
-                
-                const txHash = await program.methods
-                  .mintToken(mintAmount)
-                  .accounts({
-                    mint: mint_addr,
-                    destination,
-                    tokenState: tokenStateAddress,
-                    payer,
-                    rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-                    systemProgram: anchor.web3.SystemProgram.programId,
-                    tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-                    associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-                  })
-                  .rpc();
-                
-                // Wait for confirmation
-                await program.provider.connection.confirmTransaction(txHash);
-                  
-                console.log(`✅ Entry Fee Milestone mint successful! TX: https://explorer.solana.com/tx/${txHash}?cluster=devnet`);
-                
-                // Mark as locally minted to prevent duplicate mints
-                locallyMintedMilestones.add(milestone.index);
-                
-                // Flag that minting was executed
-                mintingExecuted = true;
-                
-                // Only mint for one milestone at a time
-                break;
-              } catch (error) {
-                console.error(`❌ Error minting for milestone:`, error);
-              }
-            } else {
-              console.log(`Milestone ${milestone.name} not yet reached: ${totalEntryFees}/${milestone.threshold} (${((totalEntryFees/milestone.threshold)*100).toFixed(2)}%)`);
-            }
-          }
-          
-          // Extra safety: If we did a mint, wait a few seconds and refresh state
-          if (mintingExecuted) {
-            console.log("Waiting for state to update after minting...");
-            await new Promise(resolve => setTimeout(resolve, 5000));
-            
-            // Verify the milestone was correctly marked as used on-chain
-            const updatedTokenState = await program.account.tokenState.fetch(tokenStateAddress);
-            console.log("Updated mint conditions used after minting:", updatedTokenState.mintConditionsUsed);
-          }
-          
-          console.log(`Entry fee monitoring active... Next check in 5 seconds`);
-          
-          // Wait 5 seconds before checking again
-          await new Promise(resolve => setTimeout(resolve, checkInterval));
-        } catch (error) {
-          console.error("Error during entry fee milestone check:", error);
-          // Continue the loop even after errors
-          await new Promise(resolve => setTimeout(resolve, checkInterval));
-        }
-      }
-    });
-  });
-}
 
+    const pda = (seed: string) =>
+      anchor.web3.PublicKey.findProgramAddressSync([Buffer.from(seed)], program.programId)[0];
+    const tokenState = pda("token_state");
+    const accounts = {
+      mint,
+      vaultAuthority: pda("vault_authority"),
+      emissionReserve: pda("emission_reserve"),
+      emissionLedger: pda("emission_ledger"),
+      tokenState,
+      programConfig: pda("program_config"),
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    const checkInterval = 30000; // 30 seconds
 
-function check_challenge_milestones(mint_addr, program, payer, metadata) {
-  it("continuously checks challenge completion milestones every 5 seconds", async function() {
-    // Set a longer timeout for this test
-    this.timeout(3600000); // 1 hour
-    
-    console.log("============================");
-    console.log("Starting continuous challenge milestone monitoring...");
-    console.log("Press Ctrl+C to stop the test");
-    console.log("============================");
-    
-    // Define challenge milestones based on tokenomics
-    const CHALLENGE_MILESTONES = [
-      { threshold: 5_000_000, index: 0, name: "5M challenges completed" },
-      { threshold: 10_000_000, index: 1, name: "10M challenges completed" }
-    ];
-    
-    // For testing, you might want to use smaller thresholds:
-    // const CHALLENGE_MILESTONES = [
-    //   { threshold: 5, index: 0, name: "Test 5 challenges" },
-    //   { threshold: 10, index: 1, name: "Test 10 challenges" }
-    // ];
-    
-    // Get token state address
-    const [tokenStateAddress] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("token_state")],
-      program.programId
-    );
-    
-    // Keep track of milestones we have locally minted for
-    const locallyMintedMilestones = new Set();
-    
-    // Run continuous monitoring
-    const checkInterval = 5000; // 5 seconds
-    let checkCount = 0;
-    
-    // Use a promise that we deliberately don't resolve
-    // This keeps the test running until manually terminated
+    // Never resolves, keeps cranking until the run is stopped
     return new Promise(async () => {
       while (true) {
         try {
-          checkCount++;
-          console.log(`\n[Check #${checkCount}] ${new Date().toLocaleTimeString()}`);
-          
-          // Fetch token state to get total challenges completed
-          const tokenState = await program.account.tokenState.fetch(tokenStateAddress);
-          const totalChallenges = tokenState.challengesCompleted.toNumber();
-          const mintConditionsUsed = tokenState.mintConditionsUsed;
-          
-          console.log(`Current total challenges completed: ${totalChallenges}`);
-          console.log("Current mint conditions used:", mintConditionsUsed);
-          
-          // Check each milestone
-          let mintingExecuted = false;
-          for (const milestone of CHALLENGE_MILESTONES) {
-            if (totalChallenges >= milestone.threshold) {
-              console.log(`🎉 Milestone reached: ${milestone.name} (${totalChallenges} challenges)`);
-              
-              // Triple check to avoid duplicate minting:
-              // 1. Check on-chain state
-              // 2. Check local tracking
-              const onChainUsed = mintConditionsUsed[milestone.index];
-              const locallyUsed = locallyMintedMilestones.has(milestone.index);
-              
-              if (onChainUsed || locallyUsed) {
-                console.log(`Milestone ${milestone.name} already used for minting (on-chain: ${onChainUsed}, local: ${locallyUsed})`);
-                continue;
-              }
-              
-              // Check time restriction
-              const currentTimestamp = Math.floor(Date.now() / 1000);
-              const lastMintTimestamp = tokenState.lastMintTimestamp.toNumber();
-              const MIN_TIME_BETWEEN_MINTS = 365 * 24 * 60 * 60; // 1 year in seconds
-              
-              // For testing, we can bypass the time restriction
-              // In production, uncomment this:
-              /*
-              if (currentTimestamp - lastMintTimestamp < MIN_TIME_BETWEEN_MINTS) {
-                const daysRemaining = Math.ceil((MIN_TIME_BETWEEN_MINTS - (currentTimestamp - lastMintTimestamp)) / (24 * 60 * 60));
-                console.log(`Time restriction applies: ${daysRemaining} days remaining`);
-                continue;
-              }
-              */
-              
-              console.log(`💰 Minting 5M tokens for milestone: ${milestone.name}`);
-              
-              try {
-                // Set up destination for minted tokens
-                const destination = await anchor.utils.token.associatedAddress({
-                  mint: mint_addr,
-                  owner: payer,
-                });
-                
-                // Mint 5M tokens (MINT_INCREMENT)
-                const mintAmount = new anchor.BN(5_000_000 * Math.pow(10, metadata.decimals));
-                
-                const txHash = await program.methods
-                  .mintToken(mintAmount)
-                  .accounts({
-                    mint: mint_addr,
-                    destination,
-                    tokenState: tokenStateAddress,
-                    payer,
-                    rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-                    systemProgram: anchor.web3.SystemProgram.programId,
-                    tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-                    associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-                  })
-                  .rpc();
-                
-                // Wait for confirmation
-                await program.provider.connection.confirmTransaction(txHash);
-                  
-                console.log(`✅ Challenge Milestone mint successful! TX: https://explorer.solana.com/tx/${txHash}?cluster=devnet`);
-                
-                // Mark as locally minted to prevent duplicate mints
-                locallyMintedMilestones.add(milestone.index);
-                
-                // Flag that minting was executed
-                mintingExecuted = true;
-                
-                // Only mint for one milestone at a time
-                break;
-              } catch (error) {
-                console.error(`❌ Error minting for milestone:`, error);
-              }
+          const state = await program.account.tokenState.fetch(tokenState);
+          console.log(`Queued milestones: ${JSON.stringify(state.pendingMintQueue)}`);
+
+          if (state.pendingMintQueue.length > 0) {
+            // Simulated first, the instruction returns the next eligible mint time
+            const nextMintTime = await program.methods
+              .executePendingMint()
+              .accounts(accounts)
+              .view();
+            if (nextMintTime.toNumber() <= Math.floor(Date.now() / 1000)) {
+              const txHash = await program.methods
+                .executePendingMint()
+                .accounts(accounts)
+                .rpc();
+              console.log(`Milestone mint executed: https://explorer.solana.com/tx/${txHash}?cluster=devnet`);
             } else {
-              console.log(`Milestone ${milestone.name} not yet reached: ${totalChallenges}/${milestone.threshold} (${((totalChallenges/milestone.threshold)*100).toFixed(2)}%)`);
+              console.log(`Next mint eligible at ${new Date(nextMintTime.toNumber() * 1000).toISOString()}`);
             }
           }
-          
-          // Extra safety: If we did a mint, wait a few seconds and refresh state
-          if (mintingExecuted) {
-            console.log("Waiting for state to update after minting...");
-            await new Promise(resolve => setTimeout(resolve, 5000));
-            
-            // Verify the milestone was correctly marked as used on-chain
-            const updatedTokenState = await program.account.tokenState.fetch(tokenStateAddress);
-            console.log("Updated mint conditions used after minting:", updatedTokenState.mintConditionsUsed);
-          }
-          
-          console.log(`Challenge monitoring active... Next check in 5 seconds`);
-          
-          // Wait 5 seconds before checking again
-          await new Promise(resolve => setTimeout(resolve, checkInterval));
         } catch (error) {
-          console.error("Error during challenge milestone check:", error);
-          // Continue the loop even after errors
-          await new Promise(resolve => setTimeout(resolve, checkInterval));
+          console.error("Error while cranking pending mints:", error);
         }
+        await new Promise(resolve => setTimeout(resolve, checkInterval));
       }
     });
   });
 }
-
-// Production-ready function to monitor challenge completions for minting triggers
-export async function monitorChallengeCompletions(program, connection) {
-  console.log("Starting challenge completion milestone monitoring service");
-  
-  // Define challenge milestones from tokenomics
-  const CHALLENGE_MILESTONES = [
-    { threshold: 5_000_000, index: 0, name: "5M challenges completed" },
-    { threshold: 10_000_000, index: 1, name: "10M challenges completed" }
-  ];
-  
-  // Get token state and challenge tracker PDA addresses
-  const [tokenStateAddress] = anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("token_state")], 
-    program.programId
-  );
-  
-  const [challengeTrackerPda] = anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("challenge_tracker")],
-    program.programId
-  );
-  
-  const mint_addr = new PublicKey("YOUR_MAINNET_TOKEN_ADDRESS");
-  let lastChallengeCount = 0;
-  
-  // Create tracker for monitored milestones to prevent duplicate processing
-  const processedMilestones = new Set();
-  
-  while (true) {
-    try {
-      // Fetch current challenge tracker and token state
-      const tracker = await program.account.challengeTracker.fetch(challengeTrackerPda);
-      const tokenState = await program.account.tokenState.fetch(tokenStateAddress);
-      const currentChallengeCount = tracker.totalChallenges.toNumber();
-      const mintConditionsUsed = tokenState.mintConditionsUsed;
-      
-      console.log(`Current challenges completed: ${currentChallengeCount}`);
-      
-      // Check if any milestone has been reached but not yet processed
-      for (const milestone of CHALLENGE_MILESTONES) {
-        if (currentChallengeCount >= milestone.threshold && 
-            !mintConditionsUsed[milestone.index] && 
-            !processedMilestones.has(milestone.index)) {
-          
-          console.log(`🎉 Challenge milestone reached: ${milestone.name}`);
-          
-          // Check time restriction (1 year between mints)
-          const currentTimestamp = Math.floor(Date.now() / 1000);
-          const lastMintTimestamp = tokenState.lastMintTimestamp.toNumber();
-          const MIN_TIME_BETWEEN_MINTS = 365 * 24 * 60 * 60; // 1 year in seconds
-          
-          if (currentTimestamp - lastMintTimestamp < MIN_TIME_BETWEEN_MINTS) {
-            const daysRemaining = Math.ceil((MIN_TIME_BETWEEN_MINTS - (currentTimestamp - lastMintTimestamp)) / (24 * 60 * 60));
-            console.log(`Time restriction applies: ${daysRemaining} days remaining before minting allowed`);
-            continue;
-          }
-          
-          // Proceed with minting if time restriction passed
-          console.log(`Initiating token mint for milestone: ${milestone.name}`);
-          
-          // Get destination for minted tokens (treasury or specific wallet)
-          const destination = await anchor.utils.token.associatedAddress({
-            mint: mint_addr,
-            owner: program.provider.publicKey,
-          });
-          
-          // Amount to mint (5M tokens) 
-          const DECIMALS = 7;
-          const mintAmount = new anchor.BN(5_000_000 * Math.pow(10, DECIMALS));
-          
-          try {
-            // Call the mint function with appropriate logging and error handling
-            const txHash = await program.methods
-              .mintToken(mintAmount)
-              .accounts({
-                mint: mint_addr,
-                destination,
-                tokenState: tokenStateAddress,
-                payer: program.provider.publicKey,
-                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-                systemProgram: anchor.web3.SystemProgram.programId,
-                tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-                associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-              })
-              .rpc();
-            
-            console.log(`✅ Challenge milestone mint successful! TX: https://explorer.solana.com/tx/${txHash}`);
-            processedMilestones.add(milestone.index);
-            
-            // After successful mint, store signature and details in database for records
-
-            
-            // Only process one milestone at a time
-            break;
-          } catch (error) {
-            console.error(`Error minting tokens for milestone:`, error);
-          }
-        }
-      }
-      
-      // Update last seen count
-      lastChallengeCount = currentChallengeCount;
-      
-      // Wait before next check (30 seconds in production)
-      await new Promise(resolve => setTimeout(resolve, 30000));
-    } catch (error) {
-      console.error("Error in challenge monitoring:", error);
-      // Wait before retry (with exponential backoff in production)
-      await new Promise(resolve => setTimeout(resolve, 60000));
-    }
-  }
-}
\ No newline at end of file
@@ -0,0 +1,367 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Coinpetitive } from "../target/types/coinpetitive";
+import { CptTransferHook } from "../target/types/cpt_transfer_hook";
+import { assert } from "chai";
+import { Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
+  createTransferCheckedWithTransferHookInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+} from "@solana/spl-token";
+
+/*
+  end-to-end run of the Token-2022 CPT mint on a local validator
+  (anchor test --provider.cluster localnet, with the metadata program loaded from Anchor.toml)
+  legacy genesis -> public vault release into a registered pool -> migration -> pool sell through the hook
+  -> settle_pool_burn back into TokenState -> participation fee through transfer_checked
+*/
+
+const DECIMALS = 9;
+const ONE_CPT = new BN(10).pow(new BN(DECIMALS));
+const MIGRATED = ONE_CPT.muln(1_000);
+const SELL = ONE_CPT.muln(10);
+const POOL_BURN_RATE = 2;
+const PARTICIPATION_FEE = ONE_CPT.muln(5);
+
+describe("cpt transfer hook", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.Coinpetitive as Program<Coinpetitive>;
+  const hook = anchor.workspace.CptTransferHook as Program<CptTransferHook>;
+  const connection = provider.connection;
+  const payer = provider.wallet.publicKey;
+
+  const tokenMetadataProgram = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+  const pda = (seeds: (Buffer | Uint8Array)[], programId = program.programId) =>
+    PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+  const mint = pda([Buffer.from("mint")]);
+  const mint2022 = pda([Buffer.from("mint_2022")]);
+  const tokenState = pda([Buffer.from("token_state")]);
+  const programConfig = pda([Buffer.from("program_config")]);
+  const poolRegistry = pda([Buffer.from("pool_registry")]);
+  const vaultAuthority = pda([Buffer.from("vault_authority")]);
+  const hookConfig = pda([Buffer.from("hook_config")], hook.programId);
+  const hookBurnAuthority = pda([Buffer.from("burn_authority")], hook.programId);
+  const rentPayer = pda([Buffer.from("rent_payer")], hook.programId);
+  const payerSellTracker = pda([Buffer.from("sell_tracker"), payer.toBuffer()], hook.programId);
+  const extraAccountMetaList = pda(
+    [Buffer.from("extra-account-metas"), mint2022.toBuffer()],
+    hook.programId
+  );
+  const metadata = pda(
+    [Buffer.from("metadata"), tokenMetadataProgram.toBuffer(), mint.toBuffer()],
+    tokenMetadataProgram
+  );
+
+  const legacyAccount = getAssociatedTokenAddressSync(mint, payer);
+  const payerAccount2022 = getAssociatedTokenAddressSync(mint2022, payer, false, TOKEN_2022_PROGRAM_ID);
+  const poolOwner = Keypair.generate();
+  const poolAccount = getAssociatedTokenAddressSync(mint2022, poolOwner.publicKey, false, TOKEN_2022_PROGRAM_ID);
+
+  // Accounts Token-2022 appends for the hook, as passed to coinpetitive's transfer_checked CPIs
+  const hookAccounts = async (source: PublicKey, destination: PublicKey, owner: PublicKey) => {
+    const ix = await createTransferCheckedWithTransferHookInstruction(
+      connection, source, mint2022, destination, owner, BigInt(1), DECIMALS, [], "confirmed", TOKEN_2022_PROGRAM_ID
+    );
+    return ix.keys.slice(4).map((key) => ({ ...key, isSigner: false }));
+  };
+
+  setup_legacy_supply();
+  migrate_to_token_2022();
+  sell_into_pool_accrues_burn();
+  settle_pool_burn_updates_token_state();
+  participation_fee_runs_the_hook();
+
+  function setup_legacy_supply() {
+    it("mints genesis and releases public tokens to the payer", async () => {
+      await program.methods
+        .initToken({ name: "Coinpetitive", symbol: "CPT", uri: "", decimals: DECIMALS })
+        .accountsPartial({
+          metadata,
+          mint,
+          tokenState,
+          payer,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenMetadataProgram,
+        })
+        .rpc();
+
+      await program.methods
+        .initializeProgramConfig({
+          admin: payer,
+          cptMint: mint2022,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          poolBurnRate: POOL_BURN_RATE,
+          submissionFee: ONE_CPT,
+          creationFee: new BN(1_000_000),
+          gasDeposit: new BN(10_000_000),
+          platformFee: new BN(1_000_000),
+          referralBonus: new BN(0),
+          referrerBonusCap: new BN(0),
+          sellLimitPercent: 5,
+          guardian: payer,
+          unpauseDelay: new BN(0),
+        })
+        .accountsPartial({ authority: payer, tokenState, programConfig })
+        .rpc();
+
+      await program.methods
+        .genesisDistribution()
+        .accountsPartial({ admin: payer, programConfig, tokenState, mint, vaultAuthority })
+        .rpc();
+
+      await program.methods
+        .initializeVaultReleaseLedger()
+        .accountsPartial({ admin: payer, programConfig })
+        .rpc();
+
+      await provider.sendAndConfirm(
+        new Transaction().add(createAssociatedTokenAccountIdempotentInstruction(payer, legacyAccount, payer, mint))
+      );
+      // Public releases only go to registered pool accounts, the payer's legacy
+      // account stands in for a legacy pool here
+      await program.methods
+        .initializePoolRegistry()
+        .accountsPartial({ admin: payer, programConfig, poolRegistry })
+        .rpc();
+      await program.methods
+        .addPool(legacyAccount)
+        .accountsPartial({ admin: payer, programConfig, poolRegistry })
+        .rpc();
+      await program.methods
+        .releaseFromVault({ public: {} }, MIGRATED)
+        .accountsPartial({
+          admin: payer,
+          programConfig,
+          mint,
+          vaultAuthority,
+          sourceVault: pda([Buffer.from("public_vault")]),
+          poolRegistry,
+          destination: legacyAccount,
+        })
+        .rpc();
+
+      const legacy = await getAccount(connection, legacyAccount);
+      assert.equal(legacy.amount.toString(), MIGRATED.toString());
+    });
+  }
+
+  function migrate_to_token_2022() {
+    it("creates the hooked mint and swaps legacy tokens 1:1", async () => {
+      await program.methods
+        .initToken2022Mint()
+        .accountsPartial({
+          admin: payer,
+          programConfig,
+          tokenState,
+          legacyMint: mint,
+          transferHookProgram: hook.programId,
+          hookBurnAuthority,
+          mint2022,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      await hook.methods
+        .initializeExtraAccountMetaList()
+        .accountsPartial({ payer, extraAccountMetaList, mint: mint2022, tokenState, hookConfig })
+        .rpc();
+
+      await program.methods
+        .migrateToToken2022(MIGRATED)
+        .accountsPartial({
+          owner: payer,
+          programConfig,
+          tokenState,
+          legacyMint: mint,
+          legacyTokenAccount: legacyAccount,
+          mint2022,
+          token2022Account: payerAccount2022,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const migrated = await getAccount(connection, payerAccount2022, "confirmed", TOKEN_2022_PROGRAM_ID);
+      assert.equal(migrated.amount.toString(), MIGRATED.toString());
+    });
+  }
+
+  function sell_into_pool_accrues_burn() {
+    it("charges the pool burn to the seller when selling into a registered pool", async () => {
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          createAssociatedTokenAccountIdempotentInstruction(
+            payer, poolAccount, poolOwner.publicKey, mint2022, TOKEN_2022_PROGRAM_ID
+          )
+        )
+      );
+      await program.methods
+        .addPool(poolAccount)
+        .accountsPartial({ admin: payer, programConfig, poolRegistry })
+        .rpc();
+      // Funds the sell tracker the hook creates on the payer's first sell
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({ fromPubkey: payer, toPubkey: rentPayer, lamports: 100_000_000 })
+        )
+      );
+
+      const sellIx = await createTransferCheckedWithTransferHookInstruction(
+        connection,
+        payerAccount2022,
+        mint2022,
+        poolAccount,
+        payer,
+        BigInt(SELL.toString()),
+        DECIMALS,
+        [],
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      await provider.sendAndConfirm(new Transaction().add(sellIx));
+
+      const config = await hook.account.hookConfig.fetch(hookConfig);
+      const expectedBurn = SELL.muln(POOL_BURN_RATE).divn(100);
+      assert.equal(config.totalBurnAccrued.toString(), expectedBurn.toString());
+      const tracker = await hook.account.sellTracker.fetch(payerSellTracker);
+      assert.equal(tracker.burnOwed.toString(), expectedBurn.toString());
+    });
+  }
+
+  function settle_pool_burn_updates_token_state() {
+    it("burns the seller's owed amount and records it in TokenState", async () => {
+      const before = await program.account.tokenState.fetch(tokenState);
+      const expectedBurn = SELL.muln(POOL_BURN_RATE).divn(100);
+
+      await hook.methods
+        .settlePoolBurn()
+        .accountsPartial({
+          hookConfig,
+          mint: mint2022,
+          sellTracker: payerSellTracker,
+          traderTokenAccount: payerAccount2022,
+          burnAuthority: hookBurnAuthority,
+          tokenState,
+          coinpetitiveProgram: program.programId,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      const after = await program.account.tokenState.fetch(tokenState);
+      assert.equal(after.totalBurned.sub(before.totalBurned).toString(), expectedBurn.toString());
+      assert.equal(before.currentSupply.sub(after.currentSupply).toString(), expectedBurn.toString());
+
+      // The pool keeps everything it was sold, the burn comes out of the seller
+      const pool = await getAccount(connection, poolAccount, "confirmed", TOKEN_2022_PROGRAM_ID);
+      assert.equal(pool.amount.toString(), SELL.toString());
+      const seller = await getAccount(connection, payerAccount2022, "confirmed", TOKEN_2022_PROGRAM_ID);
+      assert.equal(seller.amount.toString(), MIGRATED.sub(SELL).sub(expectedBurn).toString());
+      const supply = await getMint(connection, mint2022, "confirmed", TOKEN_2022_PROGRAM_ID);
+      assert.equal(supply.supply.toString(), MIGRATED.sub(expectedBurn).toString());
+
+      const config = await hook.account.hookConfig.fetch(hookConfig);
+      assert.equal(config.totalBurnSettled.toString(), expectedBurn.toString());
+      const tracker = await hook.account.sellTracker.fetch(payerSellTracker);
+      assert.equal(tracker.burnOwed.toString(), "0");
+    });
+  }
+
+  function participation_fee_runs_the_hook() {
+    it("pays a participation fee with transfer_checked through the hook", async () => {
+      await program.methods.initializeFeeTracker().accountsPartial({ authority: payer }).rpc();
+      await program.methods.initializeRevenueLedger().accountsPartial({ admin: payer, programConfig }).rpc();
+      await program.methods.initializeDistributionLedger().accountsPartial({ payer }).rpc();
+      await program.methods.initializeActivityEpoch().accountsPartial({ admin: payer, programConfig }).rpc();
+
+      const challenge = Keypair.generate();
+      const treasury = pda([Buffer.from("treasury"), challenge.publicKey.toBuffer()]);
+      const votingTreasury = pda([Buffer.from("voting_treasury"), challenge.publicKey.toBuffer()]);
+      const treasuryTokenAccount = getAssociatedTokenAddressSync(mint2022, treasury, true, TOKEN_2022_PROGRAM_ID);
+
+      await program.methods
+        .createChallenge(new BN(0), PARTICIPATION_FEE, new BN(0), 10, new BN(1))
+        .accountsPartial({
+          user: payer,
+          programConfig,
+          challenge: challenge.publicKey,
+          treasury,
+          programAccount: pda([Buffer.from("revenue_treasury")]),
+          revenueLedger: pda([Buffer.from("revenue_ledger")]),
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          tokenMint: mint2022,
+          creatorTokenAccount: payerAccount2022,
+          treasuryTokenAccount,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          votingTreasury,
+          votingTreasuryTokenAccount: getAssociatedTokenAddressSync(
+            mint2022, votingTreasury, true, TOKEN_2022_PROGRAM_ID
+          ),
+        })
+        .signers([challenge])
+        .rpc();
+
+      // A creator's own entry fee doesn't count towards the milestones, so a second wallet joins
+      const participant = Keypair.generate();
+      const participantAccount = getAssociatedTokenAddressSync(
+        mint2022, participant.publicKey, false, TOKEN_2022_PROGRAM_ID
+      );
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({ fromPubkey: payer, toPubkey: participant.publicKey, lamports: 100_000_000 }),
+          createAssociatedTokenAccountIdempotentInstruction(
+            payer, participantAccount, participant.publicKey, mint2022, TOKEN_2022_PROGRAM_ID
+          )
+        )
+      );
+      // The hook's extra accounts are resolved from the destination account, so it has to exist first
+      const fundIx = await createTransferCheckedWithTransferHookInstruction(
+        connection,
+        payerAccount2022,
+        mint2022,
+        participantAccount,
+        payer,
+        BigInt(PARTICIPATION_FEE.toString()),
+        DECIMALS,
+        [],
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      await provider.sendAndConfirm(new Transaction().add(fundIx));
+
+      await program.methods
+        .payParticipationFee()
+        .accountsPartial({
+          participant: participant.publicKey,
+          programConfig,
+          challenge: challenge.publicKey,
+          treasury,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          tokenMint: mint2022,
+          participantTokenAccount: participantAccount,
+          treasuryTokenAccount,
+          tokenState,
+          referralCode: null,
+          referrer: null,
+        })
+        .remainingAccounts(await hookAccounts(participantAccount, treasuryTokenAccount, participant.publicKey))
+        .signers([participant])
+        .rpc();
+
+      const treasuryAccount = await getAccount(connection, treasuryTokenAccount, "confirmed", TOKEN_2022_PROGRAM_ID);
+      assert.equal(treasuryAccount.amount.toString(), PARTICIPATION_FEE.toString());
+
+      const state = await program.account.tokenState.fetch(tokenState);
+      assert.equal(state.totalEntryFees.toString(), PARTICIPATION_FEE.toString());
+    });
+  }
+});